@@ -0,0 +1,2 @@
+ALTER TABLE time_entrys DROP COLUMN tid;
+DROP TABLE tasks;
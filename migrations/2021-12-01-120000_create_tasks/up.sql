@@ -0,0 +1,16 @@
+CREATE TABLE tasks (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    pid INTEGER NOT NULL,
+    wid INTEGER NOT NULL,
+    uid INTEGER,
+    estimated_seconds INTEGER,
+    active BOOLEAN NOT NULL,
+    at TEXT NOT NULL,
+    tracked_seconds INTEGER,
+
+    FOREIGN KEY(pid) REFERENCES projects(id),
+    FOREIGN KEY(wid) REFERENCES workspaces(id)
+);
+
+ALTER TABLE time_entrys ADD COLUMN tid INTEGER REFERENCES tasks(id);
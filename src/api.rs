@@ -1,13 +1,60 @@
-use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
 use reqwest::blocking;
+use reqwest::header::COOKIE;
 use reqwest::{self, StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+pub use crate::ids::{
+    ClientId, GroupId, ProjectId, ProjectUserId, SummaryGroupId, TagId, TaskId, TimeEntryId,
+    UserId, WorkspaceId,
+};
+use crate::query::{self, QueryError};
+use crate::ApiVersion;
+
+mod credentials;
+pub use credentials::{ConfigError, Credentials};
+
+pub(crate) mod endpoints;
+use endpoints::{BaseUrls, Endpoint, EndpointResult};
+
+mod logging;
+mod retry;
+pub use retry::RetryPolicy;
+use retry::{Failure, RateLimiter};
+
+mod session;
+use session::Session;
+
+#[cfg(feature = "async")]
+mod async_api;
+#[cfg(feature = "async")]
+pub use async_api::{AsyncApi, AsyncReportsDetailedIter};
+
 const API_URL: &str = "https://api.track.toggl.com/api/v8";
-const REPORTS_API_URL: &str = "https://api.track.toggl.com/reports/api/v2/details";
+const V9_API_URL: &str = "https://api.track.toggl.com/api/v9";
+const REPORTS_API_URL: &str = "https://api.track.toggl.com/reports/api/v2";
+
+/// How many time entries to update or delete per request, when doing so in bulk.
+const BULK_CHUNK_SIZE: usize = 100;
+
+/// The minimum time between two requests, by default. Toggl asks for about one request per
+/// second.
+const REQUEST_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
+/// An error answered by Toggl: an error status, or an error message in place of the expected
+/// response.
 #[derive(Debug)]
 pub struct ServerError<ErrorShape: DeserializeOwned> {
     status_code: StatusCode,
@@ -15,12 +62,93 @@ pub struct ServerError<ErrorShape: DeserializeOwned> {
     parsed_json: Option<ErrorShape>,
 }
 
+impl<ErrorShape: DeserializeOwned + ErrorMessages> ServerError<ErrorShape> {
+    pub fn status_code(&self) -> StatusCode {
+        return self.status_code;
+    }
+
+    /// The raw body of the response, if it could be fetched.
+    pub fn text(&self) -> Option<&str> {
+        return self.text.as_deref();
+    }
+
+    /// The body of the response, if it is in the shape Toggl uses for errors.
+    pub fn parsed_json(&self) -> Option<&ErrorShape> {
+        return self.parsed_json.as_ref();
+    }
+
+    /// The error messages of Toggl, e.g. "Workspace not found". Empty if the body isn't in the
+    /// shape Toggl uses for errors.
+    pub fn messages(&self) -> Vec<String> {
+        return match &self.parsed_json {
+            Some(parsed_json) => parsed_json.messages(),
+            None => vec![],
+        };
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        return match self.status_code {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ErrorKind::Auth,
+            StatusCode::NOT_FOUND => ErrorKind::NotFound,
+            StatusCode::TOO_MANY_REQUESTS => ErrorKind::RateLimited,
+            status_code if status_code.is_server_error() => ErrorKind::ServerFault,
+            // Toggl also answers some invalid requests with a 200 and error messages.
+            _ => ErrorKind::Validation,
+        };
+    }
+}
+
+impl<ErrorShape: DeserializeOwned + ErrorMessages> fmt::Display for ServerError<ErrorShape> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Toggl answered {}", self.status_code)?;
+        let messages = self.messages();
+        if !messages.is_empty() {
+            return write!(f, ": {}", messages.join("; "));
+        }
+        return match self.text() {
+            Some(text) if !text.trim().is_empty() => write!(f, ": {}", text.trim()),
+            _ => Ok(()),
+        };
+    }
+}
+
+impl<ErrorShape> error::Error for ServerError<ErrorShape> where
+    ErrorShape: DeserializeOwned + ErrorMessages + fmt::Debug
+{
+}
+
+/// A response of Toggl that isn't in the expected shape.
 #[derive(Debug)]
 pub struct ParsingError {
     text: String,
     err: Option<serde_json::error::Error>,
 }
 
+impl ParsingError {
+    /// The raw body of the response, or what went wrong if it couldn't be fetched.
+    pub fn text(&self) -> &str {
+        return &self.text;
+    }
+}
+
+impl fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match &self.err {
+            Some(err) => write!(f, "Couldn't parse the response of Toggl: {}", err),
+            None => write!(f, "{}", self.text),
+        };
+    }
+}
+
+impl error::Error for ParsingError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return self
+            .err
+            .as_ref()
+            .map(|err| err as &(dyn error::Error + 'static));
+    }
+}
+
 /// Encapsulates all errors possible when making a request
 #[derive(Debug)]
 pub enum ApiError<ErrorShape: DeserializeOwned> {
@@ -32,9 +160,141 @@ pub enum ApiError<ErrorShape: DeserializeOwned> {
 
     /// Couldn't parse server resposne
     Parsing(ParsingError),
+
+    /// Couldn't build the request: its query string, or its body
+    Query(QueryError),
+
+    /// Couldn't write the response out (report exports)
+    Io(io::Error),
+}
+
+/// What went wrong, broadly, so callers can react to an expired token differently than to a
+/// malformed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The API key (or session) was refused: 401 or 403
+    Auth,
+
+    /// 404
+    NotFound,
+
+    /// Too many requests were sent: 429
+    RateLimited,
+
+    /// Toggl refused the request: other 4xx, or error messages in place of the response
+    Validation,
+
+    /// Toggl failed: 5xx
+    ServerFault,
+
+    /// The request didn't get through, or its response didn't come back
+    Network,
+
+    /// Anything else: unexpected responses, invalid params, I/O errors, ...
+    Other,
+}
+
+impl<ErrorShape: DeserializeOwned + ErrorMessages> ApiError<ErrorShape> {
+    pub fn kind(&self) -> ErrorKind {
+        return match self {
+            ApiError::Network(_) => ErrorKind::Network,
+            ApiError::Server(err) => err.kind(),
+            ApiError::Parsing(_) | ApiError::Query(_) | ApiError::Io(_) => ErrorKind::Other,
+        };
+    }
+
+    /// The status of the response, if there was one.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        return match self {
+            ApiError::Network(err) => err.status(),
+            ApiError::Server(err) => Some(err.status_code()),
+            _ => None,
+        };
+    }
+
+    /// The raw body of the response, if there was one.
+    pub fn text(&self) -> Option<&str> {
+        return match self {
+            ApiError::Server(err) => err.text(),
+            ApiError::Parsing(err) => Some(err.text()),
+            _ => None,
+        };
+    }
+
+    /// The error messages of Toggl, if any.
+    pub fn messages(&self) -> Vec<String> {
+        return match self {
+            ApiError::Server(err) => err.messages(),
+            _ => vec![],
+        };
+    }
+}
+
+impl<ErrorShape: DeserializeOwned + ErrorMessages> fmt::Display for ApiError<ErrorShape> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ApiError::Network(err) => write!(f, "Couldn't reach Toggl: {}", err),
+            ApiError::Server(err) => write!(f, "{}", err),
+            ApiError::Parsing(err) => write!(f, "{}", err),
+            ApiError::Query(err) => write!(f, "Couldn't build the request: {}", err),
+            ApiError::Io(err) => write!(f, "Couldn't write the response out: {}", err),
+        };
+    }
+}
+
+impl<ErrorShape> error::Error for ApiError<ErrorShape>
+where
+    ErrorShape: DeserializeOwned + ErrorMessages + fmt::Debug + 'static,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return match self {
+            ApiError::Network(err) => Some(err),
+            ApiError::Server(err) => Some(err),
+            ApiError::Parsing(err) => Some(err),
+            ApiError::Query(err) => Some(err),
+            ApiError::Io(err) => Some(err),
+        };
+    }
+}
+
+/// Errors of the reports API, with their messages in the shape of the other endpoints' errors.
+/// Lets operations that go through reports (`Api::tag_merge`, ...) return a single kind of error.
+impl From<ApiError<ReportsErrorJson>> for ApiError<DefaultErrorJson> {
+    fn from(err: ApiError<ReportsErrorJson>) -> Self {
+        return match err {
+            ApiError::Network(err) => ApiError::Network(err),
+            ApiError::Server(err) => ApiError::Server(ServerError {
+                parsed_json: err.parsed_json.as_ref().map(ErrorMessages::messages),
+                status_code: err.status_code,
+                text: err.text,
+            }),
+            ApiError::Parsing(err) => ApiError::Parsing(err),
+            ApiError::Query(err) => ApiError::Query(err),
+            ApiError::Io(err) => ApiError::Io(err),
+        };
+    }
+}
+
+pub type ApiResult<BlobJson, ErrorJson> = Result<BlobJson, ApiError<ErrorJson>>;
+
+/// The shapes of Toggl's error bodies, which hold error messages.
+pub trait ErrorMessages {
+    fn messages(&self) -> Vec<String>;
+}
+
+/// Toggl's error messages are always an array of strings.
+impl ErrorMessages for Vec<String> {
+    fn messages(&self) -> Vec<String> {
+        return self.clone();
+    }
 }
 
-type ApiResult<BlobJson, ErrorJson> = Result<BlobJson, ApiError<ErrorJson>>;
+/// v9 answers a single string.
+impl ErrorMessages for String {
+    fn messages(&self) -> Vec<String> {
+        return vec![self.clone()];
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct _ReportsErrorJson {
@@ -49,17 +309,33 @@ pub struct ReportsErrorJson {
     error: _ReportsErrorJson,
 }
 
-type DefaultErrorJson = Vec<String>;
+impl ReportsErrorJson {
+    pub fn message(&self) -> &str {
+        return &self.error.message;
+    }
+
+    /// A hint at how to fix the request
+    pub fn tip(&self) -> &str {
+        return &self.error.tip;
+    }
 
-/// Trait to DRY up code to make a request, parse the JSON, and return an ApiError of the
-/// appropriate type if necessary
-trait ConsolidateApiErrors {
-    fn get_json<BlobJson, ErrorJson>(self) -> ApiResult<BlobJson, ErrorJson>
-    where
-        BlobJson: DeserializeOwned,
-        ErrorJson: DeserializeOwned;
+    /// Same as the status code
+    pub fn code(&self) -> i64 {
+        return self.error.code;
+    }
+}
+
+impl ErrorMessages for ReportsErrorJson {
+    fn messages(&self) -> Vec<String> {
+        if self.error.tip.is_empty() {
+            return vec![self.error.message.clone()];
+        }
+        return vec![self.error.message.clone(), self.error.tip.clone()];
+    }
 }
 
+type DefaultErrorJson = Vec<String>;
+
 /// Json response from server.
 enum ResponseJson<BlobJson, ErrorJson> {
     BlobJson(BlobJson),
@@ -90,60 +366,68 @@ where
     }
 }
 
-impl ConsolidateApiErrors for blocking::RequestBuilder {
-    fn get_json<BlobJson: DeserializeOwned, ErrorJson: DeserializeOwned>(
-        self,
-    ) -> Result<BlobJson, ApiError<ErrorJson>> {
-        return match self.send() {
-            Err(err) => Err(ApiError::Network(err)),
-            Ok(resp) => {
-                if resp.status() != 200 {
-                    return Err(ApiError::Server(ServerError {
-                        parsed_json: None,
-                        status_code: resp.status(),
-                        text: resp.text().ok(),
-                    }));
-                }
-                let status_code = resp.status().clone();
-                return match resp.text() {
-                    Ok(txt) => {
-                        // return Ok(serde_json::from_str::<BlobJson>(&txt).unwrap());
-                        return match serde_json::from_str::<ResponseJson<BlobJson, ErrorJson>>(&txt)
-                        {
-                            Ok(json) => match json {
-                                ResponseJson::ErrorJson(errors) => {
-                                    Err(ApiError::Server(ServerError {
-                                        parsed_json: Some(errors),
-                                        status_code,
-                                        text: None,
-                                    }))
-                                }
-                                ResponseJson::BlobJson(blob) => Ok(blob),
-                            },
-                            Err(err) => Err(ApiError::Parsing(ParsingError {
-                                text: txt,
-                                err: Some(err),
-                            })),
-                        };
-                    }
-                    Err(_) => Err(ApiError::Parsing(ParsingError {
-                        text: "Couldn't fetch response text.".to_string(),
-                        err: None,
-                    })),
-                };
-            }
-        };
+/// Parse the response of a request into `BlobJson`, or into the appropriate ApiError. `text` is
+/// `None` if the response body couldn't be fetched. Shared by the blocking and async clients.
+/// Any 2xx status is a success (Toggl answers some requests with 201 or 204).
+pub(crate) fn parse_response<BlobJson: DeserializeOwned, ErrorJson: DeserializeOwned>(
+    status_code: StatusCode,
+    text: Option<String>,
+) -> ApiResult<BlobJson, ErrorJson> {
+    if !status_code.is_success() {
+        return Err(server_error(status_code, text));
     }
+    return match text {
+        Some(txt) => {
+            // Some endpoints (DELETE, for example) answer with an empty body, which
+            // we treat as a JSON null.
+            let json_txt = if txt.trim().is_empty() { "null" } else { &txt };
+            return match serde_json::from_str::<ResponseJson<BlobJson, ErrorJson>>(json_txt) {
+                Ok(json) => match json {
+                    ResponseJson::ErrorJson(errors) => Err(ApiError::Server(ServerError {
+                        parsed_json: Some(errors),
+                        status_code,
+                        text: Some(txt),
+                    })),
+                    ResponseJson::BlobJson(blob) => Ok(blob),
+                },
+                Err(err) => Err(ApiError::Parsing(ParsingError {
+                    text: txt,
+                    err: Some(err),
+                })),
+            };
+        }
+        None => Err(ApiError::Parsing(ParsingError {
+            text: "Couldn't fetch response text.".to_string(),
+            err: None,
+        })),
+    };
+}
+
+/// The error of a request that failed with `status_code`. The body is parsed into `ErrorJson` if
+/// it is in that shape, and kept as text either way.
+pub(crate) fn server_error<ErrorJson: DeserializeOwned>(
+    status_code: StatusCode,
+    text: Option<String>,
+) -> ApiError<ErrorJson> {
+    let parsed_json = text
+        .as_deref()
+        .and_then(|txt| serde_json::from_str::<ErrorJson>(txt).ok());
+    return ApiError::Server(ServerError {
+        parsed_json,
+        status_code,
+        text,
+    });
 }
 
 // A trait to add .add_api_key to reqwest::Client
-trait AddApiKey {
-    fn add_api_key(self, api: &Api) -> Self;
+pub(crate) trait AddApiKey {
+    fn add_api_key(self, credentials: &Credentials) -> Self;
 }
 
 impl AddApiKey for blocking::RequestBuilder {
-    fn add_api_key(self, api: &Api) -> Self {
-        return self.basic_auth(api.api_key, Some("api_token"));
+    fn add_api_key(self, credentials: &Credentials) -> Self {
+        let (username, password) = credentials.basic_auth();
+        return self.basic_auth(username, Some(password));
     }
 }
 
@@ -152,7 +436,7 @@ impl AddApiKey for blocking::RequestBuilder {
 pub struct TimeEntry {
     /// The id field is not necessary when creating a time entry
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<i64>,
+    pub id: Option<TimeEntryId>,
 
     // strongly suggested to be used
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -160,15 +444,15 @@ pub struct TimeEntry {
 
     // workspace ID ( required if pid or tid not supplied)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wid: Option<i64>,
+    pub wid: Option<WorkspaceId>,
 
     // project ID ( not required)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pid: Option<i64>,
+    pub pid: Option<ProjectId>,
 
     // task ID ( not required)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tid: Option<i64>,
+    pub tid: Option<TaskId>,
 
     // not required, default false, available for pro workspaces
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -208,39 +492,142 @@ pub struct TimeEntry {
     pub at: Option<DateTime<Utc>>,
 }
 
+impl TimeEntry {
+    /// Whether the time entry is currently running (i.e., its duration is negative).
+    pub fn is_running(&self) -> bool {
+        return self.duration < 0;
+    }
+
+    /// The duration of the time entry. For a running time entry, this is the time elapsed since
+    /// it was started, computed as current_time + duration.
+    pub fn elapsed(&self) -> Duration {
+        return elapsed_from_duration(self.duration);
+    }
+}
+
+/// Toggl marks running time entries with a negative duration: minus the start time in seconds
+/// since epoch. This turns such a duration into the time elapsed since the start.
+fn elapsed_from_duration(duration: i64) -> Duration {
+    if duration < 0 {
+        return Duration::seconds(Utc::now().timestamp() + duration);
+    }
+    return Duration::seconds(duration);
+}
+
+/// The fields that can be passed when starting a time entry. The start time is set by the server.
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/time_entries.md#start-a-time-entry
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TimeEntryStart {
+    // strongly suggested to be used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    // workspace ID ( required if pid or tid not supplied)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wid: Option<WorkspaceId>,
+
+    // project ID ( not required)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<ProjectId>,
+
+    // task ID ( not required)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tid: Option<TaskId>,
+
+    // not required, default false, available for pro workspaces
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billable: Option<bool>,
+
+    // the name of your client app ( required)
+    pub created_with: String,
+
+    // a list of tag names ( not required)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
 // https://github.com/toggl/toggl_api_docs/blob/ee4d544ff9f17af2ebe278df887e3afadfe25028/chapters/clients.md#clients
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Client {
-    pub id: i64,
-    pub wid: i64,
+    /// Only sent in responses
+    #[serde(skip_serializing)]
+    pub id: Option<ClientId>,
+
+    /// workspace ID, where the client will be used (required)
+    pub wid: WorkspaceId,
+
+    /// The name of the client (required, unique in workspace)
     pub name: String,
-    pub at: DateTime<Utc>,
+
+    /// Notes for the client (not required)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// timestamp that indicates the time client was last updated (read-only)
+    #[serde(skip_serializing)]
+    pub at: Option<DateTime<Utc>>,
 }
 
-// https://github.com/toggl/toggl_api_docs/blob/master/chapters/users.md#users
+impl Client {
+    /// A client to be created.
+    pub fn new(name: String, wid: WorkspaceId) -> Self {
+        Self {
+            id: None,
+            wid,
+            name,
+            notes: None,
+            at: None,
+        }
+    }
+}
+
+/// This is the structure of the json to POST/PUT
+#[derive(Serialize, Debug)]
+struct ClientRequest<'c> {
+    client: &'c Client,
+}
+
+/// This is the structure of the json response
 #[derive(Serialize, Deserialize, Debug)]
+pub struct ClientResponse {
+    pub data: Client,
+}
+
+/// Which projects of a client to list.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectsActive {
+    /// Only active projects
+    True,
+    /// Only archived projects
+    False,
+    Both,
+}
+
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/users.md#users
+#[derive(Serialize, Deserialize)]
 pub struct User {
-    id: i64,
-    api_token: String,
-    default_wid: i64,
-    email: String,
-    fullname: String,
-    jquery_timeofday_format: String,
-    jquery_date_format: String,
-    timeofday_format: String,
-    date_format: String,
+    pub id: UserId,
+    pub api_token: String,
+    pub default_wid: WorkspaceId,
+    pub email: String,
+    pub fullname: String,
+    pub jquery_timeofday_format: String,
+    pub jquery_date_format: String,
+    pub timeofday_format: String,
+    pub date_format: String,
     /// whether start and stop time are saved on time entry
-    store_start_and_stop_time: bool,
+    pub store_start_and_stop_time: bool,
     /// integer 0-6, Sunday=0
-    beginning_of_week: i64,
+    pub beginning_of_week: i64,
     /// user's language
-    language: String,
+    pub language: String,
     /// url with the user's profile picture
-    image_url: String,
+    pub image_url: String,
     ///  should a piechart be shown on the sidebar
-    sidebar_piechart: bool,
+    pub sidebar_piechart: bool,
     /// timestamp of last changes
-    at: DateTime<Utc>,
+    pub at: DateTime<Utc>,
     ///  Toggl can send newsletters over e-mail to the user
     pub send_product_emails: bool,
     ///  if user receives weekly report
@@ -253,36 +640,119 @@ pub struct User {
     pub timezone: String,
 
     /// Extra data
-    time_entries: Option<Vec<TimeEntry>>,
-    projects: Option<Vec<Project>>,
-    tags: Option<Vec<Tag>>,
-    workspaces: Option<Vec<Workspace>>,
-    clients: Option<Vec<Client>>,
+    pub time_entries: Option<Vec<TimeEntry>>,
+    pub projects: Option<Vec<Project>>,
+    pub tags: Option<Vec<Tag>>,
+    pub workspaces: Option<Vec<Workspace>>,
+    pub clients: Option<Vec<Client>>,
+}
+
+// The api token is kept out of logs and panic messages, like `Credentials`.
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f
+            .debug_struct("User")
+            .field("id", &self.id)
+            .field("api_token", &"REDACTED")
+            .field("default_wid", &self.default_wid)
+            .field("email", &self.email)
+            .field("fullname", &self.fullname)
+            .field("jquery_timeofday_format", &self.jquery_timeofday_format)
+            .field("jquery_date_format", &self.jquery_date_format)
+            .field("timeofday_format", &self.timeofday_format)
+            .field("date_format", &self.date_format)
+            .field("store_start_and_stop_time", &self.store_start_and_stop_time)
+            .field("beginning_of_week", &self.beginning_of_week)
+            .field("language", &self.language)
+            .field("image_url", &self.image_url)
+            .field("sidebar_piechart", &self.sidebar_piechart)
+            .field("at", &self.at)
+            .field("send_product_emails", &self.send_product_emails)
+            .field("send_weekly_report", &self.send_weekly_report)
+            .field("send_timer_notifications", &self.send_timer_notifications)
+            .field("openid_enabled", &self.openid_enabled)
+            .field("timezone", &self.timezone)
+            .field("time_entries", &self.time_entries)
+            .field("projects", &self.projects)
+            .field("tags", &self.tags)
+            .field("workspaces", &self.workspaces)
+            .field("clients", &self.clients)
+            .finish();
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserResponse {
     // A unix timestamp that indicates the earliest date at which the data returned here
     // was changed.
-    since: i64,
-    data: User,
+    pub since: i64,
+    pub data: User,
+}
+
+/// The profile settings of the current user that can be changed. Fields left as `None` are left
+/// untouched.
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/users.md#update-user-data
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct UserUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fullname: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// Toggl can send newsletters over e-mail to the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_product_emails: Option<bool>,
+
+    /// if user receives weekly report
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_weekly_report: Option<bool>,
+
+    /// email user about long-running (more than 8 hours) tasks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_timer_notifications: Option<bool>,
+
+    /// whether start and stop time are saved on time entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store_start_and_stop_time: Option<bool>,
+
+    /// integer 0-6, Sunday=0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beginning_of_week: Option<i64>,
+
+    /// IANA TZ timezone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeofday_format: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+}
+
+/// This is the structure of the json to PUT
+#[derive(Serialize, Debug)]
+struct UserUpdateRequest<'u> {
+    user: &'u UserUpdate,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TotalCurrency {
-    currency: String,
-    amount: f64,
+    /// null in weekly reports without billable time
+    pub currency: Option<String>,
+    pub amount: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Report<Data> {
     #[serde(skip_serializing_if = "Option::is_none")]
-    total_grand: Option<i64>,
-    total_billable: Option<i64>,
-    total_count: i64,
-    per_page: i64,
-    total_currencies: Vec<TotalCurrency>,
-    data: Vec<Data>,
+    pub total_grand: Option<i64>,
+    pub total_billable: Option<i64>,
+    pub total_count: i64,
+    pub per_page: i64,
+    pub total_currencies: Vec<TotalCurrency>,
+    pub data: Vec<Data>,
 }
 
 /*
@@ -291,439 +761,2304 @@ pub struct Report<Data> {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ReportTimeEntry {
     /// time entry id
-    id: i64,
+    pub id: TimeEntryId,
 
     /// project id
-    pid: Option<i64>,
+    pub pid: Option<ProjectId>,
 
     /// project name for which the time entry was recorded
-    project: Option<String>,
+    pub project: Option<String>,
 
     /// client name for which the time entry was recorded
-    client: Option<String>,
+    pub client: Option<String>,
 
     /// task id
-    tid: Option<i64>,
+    pub tid: Option<TaskId>,
 
     /// task name for which the time entry was recorded
-    task: Option<String>,
+    pub task: Option<String>,
 
     /// user id whose time entry it is
-    uid: i64,
+    pub uid: UserId,
 
     /// full name of the user whose time entry it is
-    user: String,
+    pub user: String,
 
     /// time entry description
-    description: Option<String>,
+    pub description: Option<String>,
 
     /// start time of the time entry in ISO 8601 date and time format (YYYY-MM-DDTHH:MM:SS)
-    start: DateTime<Utc>,
+    pub start: DateTime<Utc>,
 
     /// end time of the time entry in ISO 8601 date and time format (YYYY-MM-DDTHH:MM:SS)
-    end: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
 
     /// time entry duration in milliseconds
-    dur: i64,
+    pub dur: i64,
 
     /// last time the time entry was updated in ISO 8601 date and time format (YYYY-MM-DDTHH:MM:SS)
-    updated: Option<DateTime<Utc>>,
+    pub updated: Option<DateTime<Utc>>,
 
     /// if the stop time is saved on the time entry, depends on user's personal settings.
-    use_stop: bool,
+    pub use_stop: bool,
 
     /// boolean, if the time entry was billable or not
-    is_billable: bool,
+    pub is_billable: bool,
 
     /// billed amount
-    billable: f64,
+    pub billable: f64,
 
     /// billable amount currency
-    cur: String,
+    pub cur: String,
 
     /// array of tag names, which assigned for the time entry
-    tags: Vec<String>,
+    pub tags: Vec<String>,
 
     /// Undocumented on Github API docs.
-    project_color: String,
+    pub project_color: String,
 
     /// Undocumented on Github API docs.
-    project_hex_color: Option<String>,
+    pub project_hex_color: Option<String>,
 }
 
-/// This is the structure of the json to POST
-#[derive(Serialize, Deserialize, Debug)]
-struct TimeEntryRequest {
-    time_entry: TimeEntry,
+/// The title of a row in a summary or weekly report. Which fields are set depends on the
+/// grouping/subgrouping of the report.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ReportTitle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_entry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex_color: Option<String>,
 }
 
-/// This is the structure of the json response
+/// A subgroup of a summary report row.
+/// https://github.com/toggl/toggl_api_docs/blob/master/reports/summary.md#response
 #[derive(Serialize, Deserialize, Debug)]
-pub struct TimeEntryResponse {
-    data: TimeEntry,
-}
+pub struct SummaryReportItem {
+    pub title: ReportTitle,
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Workspace {
-    /// The id field is not necessary when creating a workspace
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<i64>,
+    /// time in milliseconds
+    pub time: i64,
 
-    /// the name of the workspace
-    pub name: String,
+    /// currency of the billed amount
+    pub cur: Option<String>,
 
-    /// If it's a pro workspace or not. Shows if someone is paying for the workspace or not
-    pub premium: bool,
+    /// billed amount
+    pub sum: Option<f64>,
 
-    /// shows whether currently requesting user has admin access to the workspace
-    pub admin: bool,
+    /// hourly rate
+    pub rate: Option<f64>,
+}
 
-    /// default hourly rate for workspace, won't be shown to non-admins if the only_admins_see_billable_rates flag is set to true
-    pub default_hourly_rate: f64,
+/// A group of a summary report.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummaryReportGroup {
+    /// id of the group (project, client or user id), null for entries without one
+    pub id: Option<SummaryGroupId>,
 
-    /// default currency for workspace
-    pub default_currency: String,
+    pub title: ReportTitle,
 
-    /// whether only the admins can create projects or everybody
-    pub only_admins_may_create_projects: bool,
+    /// time in milliseconds
+    pub time: i64,
 
-    /// whether only the admins can see billable rates or everybody
-    pub only_admins_see_billable_rates: bool,
+    pub total_currencies: Vec<TotalCurrency>,
 
-    /// type of rounding
-    pub rounding: i64,
+    /// The subgroups of this group
+    pub items: Vec<SummaryReportItem>,
+}
 
-    /// round up to nearest minute
-    pub rounding_minutes: i64,
+/// https://github.com/toggl/toggl_api_docs/blob/master/reports/summary.md
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummaryReport {
+    pub total_grand: Option<i64>,
+    pub total_billable: Option<i64>,
+    pub total_currencies: Vec<TotalCurrency>,
+    pub data: Vec<SummaryReportGroup>,
+}
 
-    /// timestamp that indicates the time workspace was last updated
-    pub at: DateTime<Utc>,
+/// The per-user (or per-project) breakdown of a weekly report row.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WeeklyReportDetail {
+    /// user id, set when grouping by projects
+    pub uid: Option<UserId>,
 
-    /// URL pointing to the logo [if set, otherwise omited]
-    pub logo_url: Option<String>,
+    /// project id, set when grouping by users
+    pub pid: Option<ProjectId>,
+
+    pub title: ReportTitle,
+
+    /// One value per day of the week, followed by the week total. Milliseconds or earnings
+    /// depending on the `calculate` param.
+    pub totals: Vec<Option<f64>>,
 }
 
+/// A row of a weekly report.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct Tag {
-    /// The id field is not necessary when creating a workspace
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<i64>,
+pub struct WeeklyReportRow {
+    /// user id, set when grouping by users
+    pub uid: Option<UserId>,
 
-    /// The name of the tag (unique in workspace)
-    pub name: String,
+    /// project id, set when grouping by projects
+    pub pid: Option<ProjectId>,
 
-    /// workspace ID, where the tag will be used
-    pub wid: i64,
+    pub title: ReportTitle,
+
+    /// One value per day of the week, followed by the week total.
+    pub totals: Vec<Option<f64>>,
+
+    pub details: Vec<WeeklyReportDetail>,
 }
 
+/// https://github.com/toggl/toggl_api_docs/blob/master/reports/weekly.md
 #[derive(Serialize, Deserialize, Debug)]
-pub struct Project {
-    /// The id field is not necessary when creating a workspace
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<i64>,
+pub struct WeeklyReport {
+    pub total_grand: Option<i64>,
+    pub total_billable: Option<i64>,
+    pub total_currencies: Vec<TotalCurrency>,
 
-    /// The name of the project (required, unique for client and workspace)
-    name: String,
+    /// One value per day of the week, followed by the week total.
+    pub week_totals: Vec<Option<f64>>,
 
-    /// workspace ID, where the project will be saved (required)
-    wid: i64,
+    pub data: Vec<WeeklyReportRow>,
+}
 
-    /// client ID (not required)
-    cid: Option<i64>,
+/// This is the structure of the json to POST
+#[derive(Serialize, Deserialize, Debug)]
+struct TimeEntryRequest {
+    time_entry: TimeEntry,
+}
 
-    /// whether the project is archived or not (by default true)
-    active: bool,
+/// This is the structure of the json response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimeEntryResponse {
+    pub data: TimeEntry,
+}
 
-    /// whether project is accessible for only project users or for all workspace users (default true)
-    is_private: bool,
+/// This is the structure of the json response of bulk time entry updates
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TimeEntriesResponse {
+    pub data: Vec<TimeEntry>,
+}
 
-    /// whether the project can be used as a template (not required)
+/// Whether the tags of a bulk update are added to, or removed from the time entries.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TagAction {
+    Add,
+    Remove,
+}
+
+/// The fields to change when updating time entries in bulk. Fields left as `None` are left
+/// untouched.
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/time_entries.md#bulk-update-time-entries-tags
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TimeEntryPatch {
     #[serde(skip_serializing_if = "Option::is_none")]
-    template: Option<bool>,
+    pub description: Option<String>,
 
-    /// id of the template project used on current project's creation
     #[serde(skip_serializing_if = "Option::is_none")]
-    template_id: Option<i64>,
+    pub wid: Option<WorkspaceId>,
 
-    /// whether the project is billable or not (default true, available only for pro workspaces)
-    billable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<ProjectId>,
 
-    /// whether the estimated hours are automatically calculated based on task estimations or manually fixed based on the value of 'estimated_hours' (default false, not required, premium functionality)
     #[serde(skip_serializing_if = "Option::is_none")]
-    auto_estimates: Option<bool>,
+    pub tid: Option<TaskId>,
 
-    /// if auto_estimates is true then the sum of task estimations is returned, otherwise user inserted hours (not required, premium functionality)
     #[serde(skip_serializing_if = "Option::is_none")]
-    estimated_hours: Option<i64>,
+    pub billable: Option<bool>,
 
-    /// timestamp that is sent in the response for PUT, indicates the time task was last updated (read-only)
-    at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
 
-    /// id of the color selected for the project
-    color: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<DateTime<Utc>>,
 
-    /// hourly rate of the project (not required, premium functionality)
     #[serde(skip_serializing_if = "Option::is_none")]
-    rate: Option<f64>,
+    pub duration: Option<i64>,
 
-    /// timestamp indicating when the project was created (UTC time), read-only
-    created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duronly: Option<bool>,
+
+    /// With `tag_action` unset, these replace the tags of the time entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// Whether `tags` are added to, or removed from the tags of the time entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_action: Option<TagAction>,
 }
 
-/// The main Api object
-pub struct Api<'a> {
-    api_key: &'a str,
-    client: blocking::Client,
+#[derive(Serialize, Debug)]
+struct TimeEntryPatchRequest<'p> {
+    time_entry: &'p TimeEntryPatch,
 }
 
-#[derive(Serialize, Debug, Default)]
-pub struct ReportsParams {
-    // Required. The name of your application or your email address so we can get in touch in case you're doing something wrong.
-    user_agent: String,
-    // Required. The workspace whose data you want to access.
-    workspace_id: i64,
+/// A request of a bulk operation that failed, along with every id it was about: all of them
+/// failed. The ids are time entry ids, unless stated otherwise by the operation.
+#[derive(Debug)]
+pub struct BulkFailure<Id = TimeEntryId> {
+    pub ids: Vec<Id>,
+    pub error: ApiError<DefaultErrorJson>,
+}
 
-    /// ISO 8601 date (YYYY-MM-DD) format. Defaults to today - 6 days.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    since: Option<DateTime<Utc>>,
+/// The outcome of a bulk operation, which is split into several requests. Some of them can
+/// succeed while others fail.
+#[derive(Debug)]
+pub struct BulkResult<Success, Id = TimeEntryId> {
+    pub succeeded: Vec<Success>,
+    pub failed: Vec<BulkFailure<Id>>,
+}
 
-    /// ISO 8601 date (YYYY-MM-DD) format. Note: Maximum date span (until - since) is one year.
-    /// Defaults to today, unless since is in future or more than year ago, in this case until is since + 6 days.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    until: Option<DateTime<Utc>>,
+impl<Success, Id: Copy> BulkResult<Success, Id> {
+    pub(crate) fn new() -> Self {
+        Self {
+            succeeded: vec![],
+            failed: vec![],
+        }
+    }
 
-    /// "yes", "no", or "both". Defaults to "both".
-    #[serde(skip_serializing_if = "Option::is_none")]
-    billable: Option<String>,
+    /// Record the outcome of the request about `ids`.
+    pub(crate) fn record<Successes: IntoIterator<Item = Success>>(
+        &mut self,
+        ids: &[Id],
+        outcome: ApiResult<Successes, DefaultErrorJson>,
+    ) {
+        match outcome {
+            Ok(successes) => self.succeeded.extend(successes),
+            Err(error) => self.failed.push(BulkFailure {
+                ids: ids.to_vec(),
+                error,
+            }),
+        }
+    }
 
-    /// A list of client IDs separated by a comma. Use "0" if you want to filter out time entries without a client.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    client_ids: Option<Vec<i64>>,
+    /// The ids the operation failed for.
+    pub fn failed_ids(&self) -> Vec<Id> {
+        return self
+            .failed
+            .iter()
+            .flat_map(|failure| failure.ids.iter().copied())
+            .collect();
+    }
 
-    /// A list of project IDs separated by a comma. Use "0" if you want to filter out time entries without a project.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    project_ids: Option<Vec<i64>>,
+    /// The successes if nothing failed, otherwise the first error.
+    pub fn into_result(self) -> ApiResult<Vec<Success>, DefaultErrorJson> {
+        return match self.failed.into_iter().next() {
+            Some(failure) => Err(failure.error),
+            None => Ok(self.succeeded),
+        };
+    }
+}
 
-    /// A list of user IDs separated by a comma.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    user_ids: Option<Vec<i64>>,
+/// This is the structure of the json to POST when starting a time entry
+#[derive(Serialize, Deserialize, Debug)]
+struct TimeEntryStartRequest {
+    time_entry: TimeEntryStart,
+}
 
-    /// A list of group IDs separated by a comma. This limits provided user_ids to the members of the given groups.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    members_of_group_ids: Option<Vec<i64>>,
+/// This is the structure of the json response when fetching the running time entry. `data` is
+/// null when no time entry is running.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CurrentTimeEntryResponse {
+    pub data: Option<TimeEntry>,
+}
 
-    /// A list of group IDs separated by a comma. This extends provided user_ids with the members of the given groups.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Workspace {
+    /// The id field is not necessary when creating a workspace
     #[serde(skip_serializing_if = "Option::is_none")]
-    or_members_of_group_ids: Option<Vec<i64>>,
+    pub id: Option<WorkspaceId>,
 
-    /// A list of tag IDs separated by a comma. Use "0" if you want to filter out time entries without a tag.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    tag_ids: Option<Vec<i64>>,
+    /// the name of the workspace
+    pub name: String,
 
-    /// A list of task IDs separated by a comma. Use "0" if you want to filter out time entries without a task.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    task_ids: Option<Vec<i64>>,
+    /// If it's a pro workspace or not. Shows if someone is paying for the workspace or not
+    pub premium: bool,
 
-    /// A list of time entry IDs separated by a comma.
+    /// shows whether currently requesting user has admin access to the workspace
+    pub admin: bool,
+
+    /// default hourly rate for workspace, won't be shown to non-admins if the only_admins_see_billable_rates flag is set to true
+    pub default_hourly_rate: f64,
+
+    /// default currency for workspace
+    pub default_currency: String,
+
+    /// whether only the admins can create projects or everybody
+    pub only_admins_may_create_projects: bool,
+
+    /// whether only the admins can see billable rates or everybody
+    pub only_admins_see_billable_rates: bool,
+
+    /// type of rounding
+    pub rounding: i64,
+
+    /// round up to nearest minute
+    pub rounding_minutes: i64,
+
+    /// timestamp that indicates the time workspace was last updated
+    pub at: DateTime<Utc>,
+
+    /// URL pointing to the logo [if set, otherwise omited]
+    pub logo_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tag {
+    /// The id field is not necessary when creating a workspace
     #[serde(skip_serializing_if = "Option::is_none")]
-    time_entry_ids: Option<Vec<i64>>,
+    pub id: Option<TagId>,
 
-    /// Matches against time entry descriptions.
+    /// The name of the tag (unique in workspace)
+    pub name: String,
+
+    /// workspace ID, where the tag will be used
+    pub wid: WorkspaceId,
+}
+
+/// Errors of `Api::tag_rename`.
+#[derive(Debug)]
+pub enum TagRenameError {
+    /// The workspace already has this other tag of the requested name
+    NameTaken(Tag),
+
+    /// Listing the tags or renaming failed
+    Api(ApiError<DefaultErrorJson>),
+}
+
+impl From<ApiError<DefaultErrorJson>> for TagRenameError {
+    fn from(err: ApiError<DefaultErrorJson>) -> Self {
+        return TagRenameError::Api(err);
+    }
+}
+
+impl fmt::Display for TagRenameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            TagRenameError::NameTaken(tag) => {
+                write!(f, "The workspace already has a tag called {}", tag.name)
+            }
+            TagRenameError::Api(err) => write!(f, "{}", err),
+        };
+    }
+}
+
+impl error::Error for TagRenameError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return match self {
+            TagRenameError::NameTaken(_) => None,
+            TagRenameError::Api(err) => Some(err),
+        };
+    }
+}
+
+/// This is the structure of the json to POST/PUT
+#[derive(Serialize, Debug)]
+struct TagRequest<'t> {
+    tag: &'t Tag,
+}
+
+/// This is the structure of the json response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TagResponse {
+    pub data: Tag,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Project {
+    /// Only sent in responses
+    #[serde(skip_serializing)]
+    pub id: Option<ProjectId>,
+
+    /// The name of the project (required, unique for client and workspace)
+    pub name: String,
+
+    /// workspace ID, where the project will be saved (required)
+    pub wid: WorkspaceId,
+
+    /// client ID (not required)
     #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
+    pub cid: Option<ClientId>,
 
-    /// "true" or "false". Filters out the time entries which do not have a description (literally "(no description)").
+    /// whether the project is archived or not (by default true)
+    pub active: bool,
+
+    /// whether project is accessible for only project users or for all workspace users (default true)
+    pub is_private: bool,
+
+    /// whether the project can be used as a template (not required)
     #[serde(skip_serializing_if = "Option::is_none")]
-    without_description: Option<bool>,
+    pub template: Option<bool>,
 
-    /// For detailed reports: "date", "description", "duration", or "user"
-    /// For summary reports: "title", "duration", or "amount"
-    /// For weekly reports: "title", "day1", "day2", "day3", "day4", "day5", "day6", "day7", or "week_total"
+    /// id of the template project used on current project's creation
     #[serde(skip_serializing_if = "Option::is_none")]
-    order_field: Option<String>,
+    pub template_id: Option<ProjectId>,
 
-    /// "on" for descending, or "off" for ascending order.
+    /// whether the project is billable or not (default true, available only for pro workspaces)
+    pub billable: bool,
+
+    /// whether the estimated hours are automatically calculated based on task estimations or manually fixed based on the value of 'estimated_hours' (default false, not required, premium functionality)
     #[serde(skip_serializing_if = "Option::is_none")]
-    order_desc: Option<String>,
+    pub auto_estimates: Option<bool>,
 
-    /// "on" or "off". Defaults to "off".
+    /// if auto_estimates is true then the sum of task estimations is returned, otherwise user inserted hours (not required, premium functionality)
     #[serde(skip_serializing_if = "Option::is_none")]
-    distinct_rates: Option<String>,
+    pub estimated_hours: Option<i64>,
 
-    /// "on" or "off". Defaults to "off". Rounds time according to workspace settings.
+    /// timestamp that is sent in the response for PUT, indicates the time task was last updated (read-only)
+    #[serde(skip_serializing)]
+    pub at: Option<DateTime<Utc>>,
+
+    /// id of the color selected for the project (not required)
     #[serde(skip_serializing_if = "Option::is_none")]
-    rounding: Option<String>,
+    pub color: Option<String>,
 
-    /// "decimal" or "minutes". Defaults to "minutes". Determines whether to display hours as a decimal number or with minutes.
+    /// hourly rate of the project (not required, premium functionality)
     #[serde(skip_serializing_if = "Option::is_none")]
-    display_hours: Option<String>,
+    pub rate: Option<f64>,
+
+    /// timestamp indicating when the project was created (UTC time), read-only
+    #[serde(skip_serializing)]
+    pub created_at: Option<DateTime<Utc>>,
 }
 
-// We use serde here to make it easier to build the URL
-impl ReportsParams {
-    pub fn new(user_agent: String, workspace_id: i64) -> Self {
+impl Project {
+    /// A project to be created, with Toggl's defaults for the other fields.
+    pub fn new(name: String, wid: WorkspaceId) -> Self {
         Self {
-            user_agent,
-            workspace_id,
-            ..Default::default()
+            id: None,
+            name,
+            wid,
+            cid: None,
+            active: true,
+            is_private: true,
+            template: None,
+            template_id: None,
+            billable: true,
+            auto_estimates: None,
+            estimated_hours: None,
+            at: None,
+            color: None,
+            rate: None,
+            created_at: None,
         }
     }
 }
 
-// We use serde here to make it easier to build the URL
+/// This is the structure of the json to POST/PUT
 #[derive(Serialize, Debug)]
-pub struct ReportsDetailedParams {
-    #[serde(flatten)]
-    reports_params: ReportsParams,
-    page: i64,
+struct ProjectRequest<'p> {
+    project: &'p Project,
 }
 
-impl ReportsDetailedParams {
-    pub fn new(user_agent: String, workspace_id: i64, page: i64) -> Self {
-        Self {
-            reports_params: ReportsParams::new(user_agent, workspace_id),
-            page,
-        }
-    }
+/// Only sets `active`, to (un)archive a project.
+#[derive(Serialize, Debug)]
+struct ProjectActive {
+    active: bool,
+}
 
-    pub fn to_url(&self) -> Url {
-        let json = serde_json::to_value(self).unwrap();
-        let mut query_params = vec![];
-        if let serde_json::Value::Object(map) = json {
-            for (key, wrapped_val) in map.into_iter() {
-                if serde_json::Value::Null == wrapped_val {
-                    continue;
-                };
-                let to_append = match wrapped_val {
-                    serde_json::Value::Bool(val) => Some(val.to_string()),
-                    serde_json::Value::Number(val) => Some(val.to_string()),
-                    serde_json::Value::String(val) => Some(val),
-                    serde_json::Value::Array(val) => Some(
-                        val.into_iter()
-                            .map(|x| {
-                                if let serde_json::Value::String(val) = x {
-                                    val
-                                } else {
-                                    panic!("Shouldn't happen.")
-                                }
-                            })
-                            .collect::<Vec<String>>()
-                            .join(","),
-                    ),
-                    serde_json::Value::Object(val) => {
-                        panic!("Key {} had unexpcted val {:?}", key, val)
-                    }
-                    serde_json::Value::Null => None,
-                };
+#[derive(Serialize, Debug)]
+struct ProjectActiveRequest {
+    project: ProjectActive,
+}
 
-                if let Some(item) = to_append {
-                    query_params.push((key, item));
-                };
-            }
-        } else {
-            panic!("unexpected val: {:?}", json)
-        }
-        return Url::parse_with_params(REPORTS_API_URL, query_params).unwrap();
-    }
+/// This is the structure of the json response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProjectResponse {
+    pub data: Project,
 }
-impl<'a> Api<'a> {
-    pub fn new(api_key: &'a str) -> Api {
-        Api {
-            api_key,
-            client: blocking::Client::new(),
-        }
-    }
 
-    fn post_and_get_json<
-        BodyJson: Serialize,
-        BlobJson: DeserializeOwned,
-        ErrorJson: DeserializeOwned,
-    >(
-        &self,
-        endpoint: &str,
-        body: &BodyJson,
-    ) -> ApiResult<BlobJson, ErrorJson> {
-        println!("Requesting: {}", endpoint);
-        let result = self
-            .client
-            .post(endpoint)
-            .add_api_key(self)
-            .json(body)
-            .get_json();
-        return result;
-    }
+/// The file formats reports can be exported to.
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    Pdf,
+    Csv,
+    Xlsx,
+}
 
-    /// Create a time entry. Look at `TimeEntry`'s documentation for fields that are required.
-    pub fn time_entry_create(
-        &self,
-        time_entry: &TimeEntry,
-    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
-        let endpoint = API_URL.to_owned() + "/time_entries";
-        println!("Requesting: {}", endpoint);
-        let result = self.post_and_get_json(
-            &endpoint,
-            &TimeEntryRequest {
-                time_entry: time_entry.clone(),
-            },
-        );
-        return result;
+impl ExportFormat {
+    /// The extension of the file, which is also how the format is picked in the reports API.
+    pub fn extension(&self) -> &'static str {
+        return match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        };
     }
+}
 
-    /// Get workspaces
-    pub fn workspaces_get_all(&self) -> ApiResult<Vec<Workspace>, DefaultErrorJson> {
-        let endpoint = API_URL.to_owned() + "/workspaces";
-        println!("Requesting: {}", endpoint);
-        let result = self.client.get(endpoint).add_api_key(self).get_json();
-        return result;
-    }
+/// Params of the reports that can be exported to a file.
+pub trait ReportsExportParams: Serialize + Sized {
+    /// The path of the export, relative to the reports API.
+    fn export_path(&self, format: ExportFormat) -> String;
+}
 
-    /// Get workspace tags
-    pub fn workspaces_tags_all(&self, wid: i64) -> ApiResult<Vec<Tag>, DefaultErrorJson> {
-        let endpoint = API_URL.to_owned() + "/workspaces/" + &wid.to_string() + "/tags";
-        println!("Requesting: {}", endpoint);
-        let result = self.client.get(endpoint).add_api_key(self).get_json();
-        return result;
+impl ReportsExportParams for ReportsDetailedParams {
+    fn export_path(&self, format: ExportFormat) -> String {
+        return format!("/details.{}", format.extension());
     }
+}
 
-    /// Get workspace projects
-    pub fn workspaces_projects_all(&self, wid: i64) -> ApiResult<Vec<Project>, DefaultErrorJson> {
-        let endpoint = API_URL.to_owned() + "/workspaces/" + &wid.to_string() + "/projects";
-        println!("Requesting: {}", endpoint);
-        let result = self.client.get(endpoint).add_api_key(self).get_json();
-        return result;
+impl ReportsExportParams for ReportsSummaryParams {
+    fn export_path(&self, format: ExportFormat) -> String {
+        return format!("/summary.{}", format.extension());
     }
+}
 
-    /// Get reports
-    pub fn reports_detailed(
-        &self,
-        params: &ReportsDetailedParams,
-    ) -> ApiResult<Report<ReportTimeEntry>, ReportsErrorJson> {
-        let endpoint = params.to_url();
-        println!("Requesting: {}", endpoint);
-        return self.client.get(endpoint).add_api_key(self).get_json();
+impl ReportsExportParams for ReportsWeeklyParams {
+    fn export_path(&self, format: ExportFormat) -> String {
+        return format!("/weekly.{}", format.extension());
     }
+}
 
-    /// Get current user
+/// The query params of `Api::time_entries`
+#[derive(Serialize, Debug)]
+struct TimeEntriesParams {
+    /// ISO 8601 date and time
+    start_date: DateTime<Utc>,
+    /// ISO 8601 date and time
+    end_date: DateTime<Utc>,
+}
+
+/// The query params of `Api::current_user`
+#[derive(Serialize, Debug)]
+struct CurrentUserParams {
+    with_related_data: bool,
+    /// unix timestamp
+    since: i64,
+}
+
+/// The query params of `Api::client_projects`
+#[derive(Serialize, Debug)]
+struct ClientProjectsParams {
+    active: ProjectsActive,
+}
+
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/tasks.md#tasks
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Task {
+    /// Only sent in responses
+    #[serde(skip_serializing)]
+    pub id: Option<TaskId>,
+
+    /// The name of the task (required, unique in project)
+    pub name: String,
+
+    /// project ID for the task (required)
+    pub pid: ProjectId,
+
+    /// workspace ID, where the task will be saved (project's workspace id is used when not
+    /// supplied)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wid: Option<WorkspaceId>,
+
+    /// user ID, to whom the task is assigned to (not required)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<UserId>,
+
+    /// estimated duration of task in seconds (not required)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_seconds: Option<i64>,
+
+    /// whether the task is done or not (by default true)
+    pub active: bool,
+
+    /// timestamp that is sent in the response for PUT, indicates the time task was last updated
+    /// (read-only)
+    #[serde(skip_serializing)]
+    pub at: Option<DateTime<Utc>>,
+
+    /// total time tracked (in seconds) for the task (read-only)
+    #[serde(skip_serializing)]
+    pub tracked_seconds: Option<i64>,
+}
+
+impl Task {
+    /// A task to be created.
+    pub fn new(name: String, pid: ProjectId) -> Self {
+        Self {
+            id: None,
+            name,
+            pid,
+            wid: None,
+            uid: None,
+            estimated_seconds: None,
+            active: true,
+            at: None,
+            tracked_seconds: None,
+        }
+    }
+}
+
+/// Which tasks to list. Toggl only lists active tasks by default.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TasksActive {
+    /// Only active tasks
+    True,
+    /// Only done tasks
+    False,
+    Both,
+}
+
+/// The query params of `Api::projects_tasks_all` and `Api::workspaces_tasks_all`
+#[derive(Serialize, Debug)]
+struct TasksParams {
+    active: TasksActive,
+}
+
+/// This is the structure of the json to POST/PUT
+#[derive(Serialize, Debug)]
+struct TaskRequest<'t> {
+    task: &'t Task,
+}
+
+/// This is the structure of the json response
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TaskResponse {
+    pub data: Task,
+}
+
+/// A user of a workspace, as listed by `Api::workspaces_users_all`.
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/workspaces.md#get-workspace-users
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WorkspaceUser {
+    pub id: UserId,
+    pub email: String,
+    pub fullname: String,
+
+    /// the user's default workspace
+    pub default_wid: Option<WorkspaceId>,
+
+    /// timezone user has set on the "My profile" page ( IANA TZ timezones )
+    pub timezone: Option<String>,
+
+    /// url with the user's profile picture
+    pub image_url: Option<String>,
+
+    /// timestamp of last changes
+    pub at: Option<DateTime<Utc>>,
+}
+
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/project_users.md#project-users
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProjectUser {
+    pub id: ProjectUserId,
+
+    /// project ID
+    pub pid: ProjectId,
+
+    /// user ID, who is added to the project
+    pub uid: UserId,
+
+    /// workspace ID, where the project belongs to
+    pub wid: WorkspaceId,
+
+    /// admin rights for this project
+    pub manager: bool,
+
+    /// hourly rate for the project user in the currency of the project's client or in
+    /// workspace default currency (premium functionality)
+    pub rate: Option<f64>,
+
+    /// timestamp that is sent in the response, indicates when the project user was last updated
+    pub at: Option<DateTime<Utc>>,
+
+    /// full name of the user, who is added to the project
+    pub fullname: Option<String>,
+}
+
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/groups.md#groups
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Group {
+    pub id: GroupId,
+
+    /// The name of the group (unique in workspace)
+    pub name: String,
+
+    /// workspace ID, where the group will be used
+    pub wid: WorkspaceId,
+
+    /// timestamp that is sent in the response, indicates the time group was last updated
+    pub at: Option<DateTime<Utc>>,
+}
+
+/// The query params to also fetch the full names of project users
+#[derive(Serialize, Debug)]
+struct ProjectUsersParams {
+    fields: &'static str,
+}
+
+/// A user of the workspace, along with how much they tracked.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MostActiveUser {
+    pub user_id: UserId,
+
+    /// tracked time in seconds
+    pub duration: i64,
+}
+
+/// A time entry recently tracked in the workspace.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Activity {
+    pub user_id: UserId,
+    pub project_id: Option<ProjectId>,
+
+    /// task id
+    pub tid: Option<TaskId>,
+
+    /// Same as `TimeEntry::duration`: negative if the time entry is running.
+    pub duration: i64,
+
+    pub description: Option<String>,
+
+    /// null if the time entry is running
+    pub stop: Option<DateTime<Utc>>,
+}
+
+impl Activity {
+    /// Whether the time entry is currently running (i.e., its duration is negative).
+    pub fn is_running(&self) -> bool {
+        return self.duration < 0;
+    }
+
+    /// The duration of the time entry, or the time elapsed since it was started if it's running.
+    pub fn elapsed(&self) -> Duration {
+        return elapsed_from_duration(self.duration);
+    }
+}
+
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/dashboard.md#dashboard
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Dashboard {
+    /// The users who tracked the most time during the last 7 days
+    pub most_active_user: Option<Vec<MostActiveUser>>,
+
+    /// The latest time entries of the workspace users
+    pub activity: Option<Vec<Activity>>,
+}
+
+/// The main Api object. Clones are cheap, and share the connection pool and the rate limit.
+#[derive(Clone)]
+pub struct Api {
+    credentials: Credentials,
+    client: blocking::Client,
+    base_urls: BaseUrls,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    session: Arc<Session>,
+    /// The version of the API sessions are opened with
+    version: ApiVersion,
+}
+
+/// Builds an `Api` (or an `AsyncApi`, or a `crate::v9::Api`) that doesn't use the defaults: other base URLs (a local
+/// mock server, a proxy, ...), a user agent, timeouts, a pre-configured reqwest client, or
+/// another rate limit and retry policy. Created with `ApiBuilder::new`, or `Api::builder`.
+pub struct ApiBuilder {
+    credentials: Credentials,
+    base_urls: BaseUrls,
+    request_interval: std::time::Duration,
+    retry_policy: RetryPolicy,
+    user_agent: Option<String>,
+    timeout: Option<std::time::Duration>,
+    connect_timeout: Option<std::time::Duration>,
+    client: Option<blocking::Client>,
+    #[cfg(feature = "async")]
+    async_client: Option<reqwest::Client>,
+}
+
+impl ApiBuilder {
+    /// Start building an `Api` that authenticates with `credentials`, e.g. to log in with an email
+    /// and password. `Api::builder` is the shorthand for an API token.
+    pub fn new(credentials: Credentials) -> Self {
+        return ApiBuilder {
+            credentials,
+            base_urls: BaseUrls::default(),
+            request_interval: REQUEST_INTERVAL,
+            retry_policy: RetryPolicy::default(),
+            user_agent: None,
+            timeout: None,
+            connect_timeout: None,
+            client: None,
+            #[cfg(feature = "async")]
+            async_client: None,
+        };
+    }
+
+    /// The base URL of the Toggl Track API, `https://api.track.toggl.com/api/v8` by default.
+    pub fn track_url(mut self, url: &str) -> Self {
+        self.base_urls.track = url.trim_end_matches('/').to_owned();
+        return self;
+    }
+
+    /// The base URL of v9 of the Toggl Track API, used by `build_v9`.
+    /// `https://api.track.toggl.com/api/v9` by default.
+    pub fn v9_url(mut self, url: &str) -> Self {
+        self.base_urls.track_v9 = url.trim_end_matches('/').to_owned();
+        return self;
+    }
+
+    /// The base URL of the reports API, `https://api.track.toggl.com/reports/api/v2` by default.
+    pub fn reports_url(mut self, url: &str) -> Self {
+        self.base_urls.reports = url.trim_end_matches('/').to_owned();
+        return self;
+    }
+
+    /// The User-Agent header sent with every request. Ignored if a client is passed.
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = Some(user_agent);
+        return self;
+    }
+
+    /// The timeout of a whole request, from connecting to reading the response. Ignored if a
+    /// client is passed.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        return self;
+    }
+
+    /// The timeout of connecting to the server. Ignored if a client is passed.
+    pub fn connect_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        return self;
+    }
+
+    /// The minimum time between two requests, one second by default. Zero disables rate limiting.
+    pub fn min_request_interval(mut self, interval: std::time::Duration) -> Self {
+        self.request_interval = interval;
+        return self;
+    }
+
+    /// How failed requests are retried. See `RetryPolicy` for the defaults.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        return self;
+    }
+
+    /// Send requests with `client`, e.g. to use a proxy or custom certificates. It is used as is,
+    /// so its user agent and timeouts have to be set on it.
+    pub fn client(mut self, client: blocking::Client) -> Self {
+        self.client = Some(client);
+        return self;
+    }
+
+    /// Same as `client`, for `build_async`.
+    #[cfg(feature = "async")]
+    pub fn async_client(mut self, client: reqwest::Client) -> Self {
+        self.async_client = Some(client);
+        return self;
+    }
+
+    /// Build the `Api`. Fails if the reqwest client can't be built (e.g. the TLS backend can't be
+    /// initialized).
+    pub fn build(self) -> Result<Api, reqwest::Error> {
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = blocking::Client::builder();
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                builder.build()?
+            }
+        };
+        return Ok(Api {
+            credentials: self.credentials,
+            client,
+            base_urls: self.base_urls,
+            rate_limiter: Arc::new(RateLimiter::new(self.request_interval)),
+            retry_policy: self.retry_policy,
+            session: Arc::default(),
+            version: ApiVersion::V8,
+        });
+    }
+
+    /// Build a client of v9 of the API, which is rate limited, retried and configured the same
+    /// way as an `Api`. Fails if the reqwest client can't be built.
+    pub fn build_v9(self) -> Result<crate::v9::Api, reqwest::Error> {
+        return Ok(crate::v9::Api::from_v8(self.build()?));
+    }
+
+    /// Build an `AsyncApi`. Fails if the reqwest client can't be built.
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<AsyncApi, reqwest::Error> {
+        let client = match self.async_client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                builder.build()?
+            }
+        };
+        return Ok(AsyncApi::from_parts(
+            self.credentials,
+            client,
+            self.base_urls,
+            Arc::new(RateLimiter::new(self.request_interval)),
+            self.retry_policy,
+        ));
+    }
+}
+
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct ReportsParams {
+    // Required. The name of your application or your email address so we can get in touch in case you're doing something wrong.
+    user_agent: String,
+    // Required. The workspace whose data you want to access.
+    workspace_id: WorkspaceId,
+
+    /// ISO 8601 date (YYYY-MM-DD) format. Defaults to today - 6 days.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "query::date"
+    )]
+    since: Option<DateTime<Utc>>,
+
+    /// ISO 8601 date (YYYY-MM-DD) format. Note: Maximum date span (until - since) is one year.
+    /// Defaults to today, unless since is in future or more than year ago, in this case until is since + 6 days.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "query::date"
+    )]
+    until: Option<DateTime<Utc>>,
+
+    /// "yes", "no", or "both". Defaults to "both".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    billable: Option<Billable>,
+
+    /// A list of client IDs separated by a comma. Use "0" if you want to filter out time entries without a client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_ids: Option<Vec<ClientId>>,
+
+    /// A list of project IDs separated by a comma. Use "0" if you want to filter out time entries without a project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_ids: Option<Vec<ProjectId>>,
+
+    /// A list of user IDs separated by a comma.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_ids: Option<Vec<UserId>>,
+
+    /// A list of group IDs separated by a comma. This limits provided user_ids to the members of the given groups.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    members_of_group_ids: Option<Vec<GroupId>>,
+
+    /// A list of group IDs separated by a comma. This extends provided user_ids with the members of the given groups.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    or_members_of_group_ids: Option<Vec<GroupId>>,
+
+    /// A list of tag IDs separated by a comma. Use "0" if you want to filter out time entries without a tag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag_ids: Option<Vec<TagId>>,
+
+    /// A list of task IDs separated by a comma. Use "0" if you want to filter out time entries without a task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task_ids: Option<Vec<TaskId>>,
+
+    /// A list of time entry IDs separated by a comma.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_entry_ids: Option<Vec<TimeEntryId>>,
+
+    /// Matches against time entry descriptions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+
+    /// "true" or "false". Filters out the time entries which do not have a description (literally "(no description)").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    without_description: Option<bool>,
+
+    /// For detailed reports: "date", "description", "duration", or "user"
+    /// For summary reports: "title", "duration", or "amount"
+    /// For weekly reports: "title", "day1", "day2", "day3", "day4", "day5", "day6", "day7", or "week_total"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_field: Option<OrderField>,
+
+    /// "on" for descending, or "off" for ascending order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_desc: Option<OrderDirection>,
+
+    /// "on" or "off". Defaults to "off".
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "query::on_off"
+    )]
+    distinct_rates: Option<bool>,
+
+    /// "on" or "off". Defaults to "off". Rounds time according to workspace settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rounding: Option<Rounding>,
+
+    /// "decimal" or "minutes". Defaults to "minutes". Determines whether to display hours as a decimal number or with minutes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_hours: Option<DisplayHours>,
+}
+
+// We use serde here to make it easier to build the URL
+impl ReportsParams {
+    pub fn new(user_agent: String, workspace_id: WorkspaceId) -> Self {
+        Self {
+            user_agent,
+            workspace_id,
+            ..Default::default()
+        }
+    }
+
+    /// Start building params with filters. See `ReportsParamsBuilder`.
+    pub fn builder(user_agent: String, workspace_id: WorkspaceId) -> ReportsParamsBuilder {
+        return ReportsParamsBuilder {
+            params: Self::new(user_agent, workspace_id),
+        };
+    }
+}
+
+/// Whether to include billable entries, non-billable entries or both in a report.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Billable {
+    Yes,
+    No,
+    Both,
+}
+
+#[derive(Serialize, Debug, Clone, Copy)]
+pub enum OrderDirection {
+    #[serde(rename = "off")]
+    Ascending,
+    #[serde(rename = "on")]
+    Descending,
+}
+
+/// Whether to round time according to the workspace settings.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    On,
+    Off,
+}
+
+/// Whether to display hours as a decimal number or with minutes.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayHours {
+    Decimal,
+    Minutes,
+}
+
+/// The fields a detailed report can be ordered by.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailedOrderField {
+    Date,
+    Description,
+    Duration,
+    User,
+}
+
+/// The fields a summary report can be ordered by.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryOrderField {
+    Title,
+    Duration,
+    Amount,
+}
+
+/// The fields a weekly report can be ordered by.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum WeeklyOrderField {
+    Title,
+    Day1,
+    Day2,
+    Day3,
+    Day4,
+    Day5,
+    Day6,
+    Day7,
+    WeekTotal,
+}
+
+/// The order field of any kind of report. The report specific params (`ReportsDetailedParams`,
+/// ...) make sure only the right kind is used.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(untagged)]
+enum OrderField {
+    Detailed(DetailedOrderField),
+    Summary(SummaryOrderField),
+    Weekly(WeeklyOrderField),
+}
+
+/// Errors found while validating `ReportsParams`.
+#[derive(Debug)]
+pub enum ReportsParamsError {
+    /// `until` is before `since`
+    UntilBeforeSince {
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    },
+
+    /// The span between `since` and `until` is more than a year, which Toggl refuses.
+    SpanTooLong {
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    },
+}
+
+/// The same day as `date`, a year later. Feb 29 becomes Feb 28.
+fn one_year_after(date: NaiveDate) -> NaiveDate {
+    return NaiveDate::from_ymd_opt(date.year() + 1, date.month(), date.day())
+        .unwrap_or_else(|| NaiveDate::from_ymd(date.year() + 1, date.month(), date.day() - 1));
+}
+
+/// Builds `ReportsParams` with filters. Created with `ReportsParams::builder`.
+#[derive(Debug, Clone)]
+pub struct ReportsParamsBuilder {
+    params: ReportsParams,
+}
+
+impl ReportsParamsBuilder {
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.params.since = Some(since);
+        return self;
+    }
+
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.params.until = Some(until);
+        return self;
+    }
+
+    pub fn billable(mut self, billable: Billable) -> Self {
+        self.params.billable = Some(billable);
+        return self;
+    }
+
+    /// Use 0 to filter out time entries without a client.
+    pub fn client_ids(mut self, ids: Vec<ClientId>) -> Self {
+        self.params.client_ids = Some(ids);
+        return self;
+    }
+
+    /// Use 0 to filter out time entries without a project.
+    pub fn project_ids(mut self, ids: Vec<ProjectId>) -> Self {
+        self.params.project_ids = Some(ids);
+        return self;
+    }
+
+    pub fn user_ids(mut self, ids: Vec<UserId>) -> Self {
+        self.params.user_ids = Some(ids);
+        return self;
+    }
+
+    /// Limits the `user_ids` to the members of the given groups.
+    pub fn members_of_group_ids(mut self, ids: Vec<GroupId>) -> Self {
+        self.params.members_of_group_ids = Some(ids);
+        return self;
+    }
+
+    /// Extends the `user_ids` with the members of the given groups.
+    pub fn or_members_of_group_ids(mut self, ids: Vec<GroupId>) -> Self {
+        self.params.or_members_of_group_ids = Some(ids);
+        return self;
+    }
+
+    /// Use 0 to filter out time entries without a tag.
+    pub fn tag_ids(mut self, ids: Vec<TagId>) -> Self {
+        self.params.tag_ids = Some(ids);
+        return self;
+    }
+
+    /// Use 0 to filter out time entries without a task.
+    pub fn task_ids(mut self, ids: Vec<TaskId>) -> Self {
+        self.params.task_ids = Some(ids);
+        return self;
+    }
+
+    pub fn time_entry_ids(mut self, ids: Vec<TimeEntryId>) -> Self {
+        self.params.time_entry_ids = Some(ids);
+        return self;
+    }
+
+    /// Matches against time entry descriptions.
+    pub fn description(mut self, description: String) -> Self {
+        self.params.description = Some(description);
+        return self;
+    }
+
+    /// Filters out the time entries which do not have a description.
+    pub fn without_description(mut self, without_description: bool) -> Self {
+        self.params.without_description = Some(without_description);
+        return self;
+    }
+
+    pub fn distinct_rates(mut self, distinct_rates: bool) -> Self {
+        self.params.distinct_rates = Some(distinct_rates);
+        return self;
+    }
+
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.params.rounding = Some(rounding);
+        return self;
+    }
+
+    pub fn display_hours(mut self, display_hours: DisplayHours) -> Self {
+        self.params.display_hours = Some(display_hours);
+        return self;
+    }
+
+    /// Validate the params. The maximum span between `since` and `until` is one year.
+    pub fn build(self) -> Result<ReportsParams, ReportsParamsError> {
+        if let (Some(since), Some(until)) = (self.params.since, self.params.until) {
+            if until < since {
+                return Err(ReportsParamsError::UntilBeforeSince { since, until });
+            }
+            if until.naive_utc().date() > one_year_after(since.naive_utc().date()) {
+                return Err(ReportsParamsError::SpanTooLong { since, until });
+            }
+        }
+        return Ok(self.params);
+    }
+}
+
+// We use serde here to make it easier to build the URL
+#[derive(Serialize, Debug, Clone)]
+pub struct ReportsDetailedParams {
+    #[serde(flatten)]
+    reports_params: ReportsParams,
+    page: i64,
+}
+
+impl ReportsDetailedParams {
+    pub fn new(user_agent: String, workspace_id: WorkspaceId, page: i64) -> Self {
+        return Self::with_params(ReportsParams::new(user_agent, workspace_id), page);
+    }
+
+    /// Use `reports_params` built with `ReportsParams::builder`.
+    pub fn with_params(reports_params: ReportsParams, page: i64) -> Self {
+        Self {
+            reports_params,
+            page,
+        }
+    }
+
+    pub fn order_by(mut self, field: DetailedOrderField, direction: OrderDirection) -> Self {
+        self.reports_params.order_field = Some(OrderField::Detailed(field));
+        self.reports_params.order_desc = Some(direction);
+        return self;
+    }
+
+    /// The page to request next.
+    pub fn page(&self) -> i64 {
+        return self.page;
+    }
+
+    pub fn set_page(&mut self, page: i64) {
+        self.page = page;
+    }
+}
+
+/// What the rows of a summary report are grouped by.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryGrouping {
+    Projects,
+    Clients,
+    Users,
+}
+
+/// What the items of each summary report row are grouped by.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum SummarySubgrouping {
+    TimeEntries,
+    Tasks,
+    Projects,
+    Users,
+    Clients,
+}
+
+// We use serde here to make it easier to build the URL
+#[derive(Serialize, Debug, Clone)]
+pub struct ReportsSummaryParams {
+    #[serde(flatten)]
+    reports_params: ReportsParams,
+    grouping: SummaryGrouping,
+    subgrouping: SummarySubgrouping,
+
+    /// "true" or "false". Whether the ids of the subgroups are returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subgrouping_ids: Option<bool>,
+
+    /// "true" or "false". Whether the time entry ids are returned for each subgroup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grouped_time_entry_ids: Option<bool>,
+}
+
+impl ReportsSummaryParams {
+    pub fn new(
+        user_agent: String,
+        workspace_id: WorkspaceId,
+        grouping: SummaryGrouping,
+        subgrouping: SummarySubgrouping,
+    ) -> Self {
+        return Self::with_params(
+            ReportsParams::new(user_agent, workspace_id),
+            grouping,
+            subgrouping,
+        );
+    }
+
+    /// Use `reports_params` built with `ReportsParams::builder`.
+    pub fn with_params(
+        reports_params: ReportsParams,
+        grouping: SummaryGrouping,
+        subgrouping: SummarySubgrouping,
+    ) -> Self {
+        Self {
+            reports_params,
+            grouping,
+            subgrouping,
+            subgrouping_ids: None,
+            grouped_time_entry_ids: None,
+        }
+    }
+
+    pub fn order_by(mut self, field: SummaryOrderField, direction: OrderDirection) -> Self {
+        self.reports_params.order_field = Some(OrderField::Summary(field));
+        self.reports_params.order_desc = Some(direction);
+        return self;
+    }
+
+    /// Whether the ids of the subgroups are returned.
+    pub fn subgrouping_ids(mut self, subgrouping_ids: bool) -> Self {
+        self.subgrouping_ids = Some(subgrouping_ids);
+        return self;
+    }
+
+    /// Whether the time entry ids are returned for each subgroup.
+    pub fn grouped_time_entry_ids(mut self, grouped_time_entry_ids: bool) -> Self {
+        self.grouped_time_entry_ids = Some(grouped_time_entry_ids);
+        return self;
+    }
+}
+
+/// What the rows of a weekly report are grouped by.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum WeeklyGrouping {
+    Users,
+    Projects,
+}
+
+/// Whether a weekly report sums up durations or earnings.
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum WeeklyCalculate {
+    Time,
+    Earnings,
+}
+
+// We use serde here to make it easier to build the URL
+#[derive(Serialize, Debug, Clone)]
+pub struct ReportsWeeklyParams {
+    #[serde(flatten)]
+    reports_params: ReportsParams,
+    grouping: WeeklyGrouping,
+    calculate: WeeklyCalculate,
+}
+
+impl ReportsWeeklyParams {
+    pub fn new(
+        user_agent: String,
+        workspace_id: WorkspaceId,
+        grouping: WeeklyGrouping,
+        calculate: WeeklyCalculate,
+    ) -> Self {
+        return Self::with_params(
+            ReportsParams::new(user_agent, workspace_id),
+            grouping,
+            calculate,
+        );
+    }
+
+    /// Use `reports_params` built with `ReportsParams::builder`.
+    pub fn with_params(
+        reports_params: ReportsParams,
+        grouping: WeeklyGrouping,
+        calculate: WeeklyCalculate,
+    ) -> Self {
+        Self {
+            reports_params,
+            grouping,
+            calculate,
+        }
+    }
+
+    pub fn order_by(mut self, field: WeeklyOrderField, direction: OrderDirection) -> Self {
+        self.reports_params.order_field = Some(OrderField::Weekly(field));
+        self.reports_params.order_desc = Some(direction);
+        return self;
+    }
+}
+impl Api {
+    pub fn new(api_key: &str) -> Api {
+        return Self::with_credentials(Credentials::ApiToken(api_key.to_owned()));
+    }
+
+    /// Log in with the email and password of the user, instead of their API token. A session is
+    /// opened with them on the first request, and the following requests send its cookie instead.
+    /// If Toggl refuses the session later on (e.g. it expired), another one is opened and the
+    /// request is sent again.
+    pub fn login(email: &str, password: &str) -> Api {
+        return Self::with_credentials(Credentials::Login {
+            email: email.to_owned(),
+            password: password.to_owned(),
+        });
+    }
+
+    pub fn with_credentials(credentials: Credentials) -> Api {
+        Api {
+            credentials,
+            client: blocking::Client::new(),
+            base_urls: BaseUrls::default(),
+            rate_limiter: Arc::new(RateLimiter::new(REQUEST_INTERVAL)),
+            retry_policy: RetryPolicy::default(),
+            session: Arc::default(),
+            version: ApiVersion::V8,
+        }
+    }
+
+    /// Authenticate with the credentials in the env. See `Credentials::from_env`.
+    pub fn from_env() -> Result<Api, ConfigError> {
+        return Ok(Self::with_credentials(Credentials::from_env()?));
+    }
+
+    /// Authenticate with the credentials in a config file. See `Credentials::from_config_file`.
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Api, ConfigError> {
+        return Ok(Self::with_credentials(Credentials::from_config_file(path)?));
+    }
+
+    /// What the requests are authenticated with.
+    pub fn credentials(&self) -> &Credentials {
+        return &self.credentials;
+    }
+
+    /// Open sessions with `version` of the API, for the `crate::v9::Api` that sends its requests
+    /// through this one.
+    pub(crate) fn with_version(self, version: ApiVersion) -> Api {
+        return Api { version, ..self };
+    }
+
+    /// Start building an `Api` with other base URLs, timeouts, ... See `ApiBuilder`.
+    pub fn builder(api_key: &str) -> ApiBuilder {
+        return ApiBuilder::new(Credentials::ApiToken(api_key.to_owned()));
+    }
+
+    /// Build the request of `endpoint` to `url`, authenticated with the session cookie if there
+    /// is one, and with the credentials otherwise.
+    fn request<BlobJson, ErrorJson>(
+        &self,
+        endpoint: &Endpoint<BlobJson, ErrorJson>,
+        url: Url,
+        session_cookie: Option<&str>,
+    ) -> blocking::RequestBuilder {
+        let mut request = self.client.request(endpoint.method.clone(), url);
+        request = match session_cookie {
+            Some(cookie) => request.header(COOKIE, cookie),
+            None => request.add_api_key(&self.credentials),
+        };
+        if let Some(body) = &endpoint.body {
+            request = request.json(body);
+        }
+        return request;
+    }
+
+    /// Send the request of `endpoint` when the rate limiter allows it, retrying as per the retry
+    /// policy. Returns the response, whatever its status. If Toggl refuses the session (it
+    /// expired), another one is opened and the request is sent once more.
+    fn execute<BlobJson, ErrorJson: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint<BlobJson, ErrorJson>,
+    ) -> ApiResult<blocking::Response, ErrorJson> {
+        let session_cookie = self.session_cookie()?;
+        let resp = self.execute_as(endpoint, session_cookie.as_deref())?;
+        if session_cookie.is_none() || !session::is_refused(resp.status()) {
+            return Ok(resp);
+        }
+        let session_cookie = self.session_cookie()?;
+        return self.execute_as(endpoint, session_cookie.as_deref());
+    }
+
+    /// The cookie of the session to send instead of the credentials, opening the session first if
+    /// there is none yet. `None` for API tokens, which are sent as they are.
+    fn session_cookie<ErrorJson: DeserializeOwned>(&self) -> ApiResult<Option<String>, ErrorJson> {
+        if let Credentials::ApiToken(_) = self.credentials {
+            return Ok(None);
+        }
+        if let Some(cookie) = self.session.cookie() {
+            return Ok(Some(cookie));
+        }
+        // Concurrent requests may open a session each: the last one is kept.
+        let resp = self.execute_as(&endpoints::session_create(self.version)?, None)?;
+        let status_code = resp.status();
+        if status_code.is_success() {
+            if let Some(cookie) = self.session.open(resp.headers()) {
+                return Ok(Some(cookie));
+            }
+        }
+        return Err(server_error(status_code, resp.text().ok()));
+    }
+
+    /// Same as `execute`, authenticated with `session_cookie` if set, and with the credentials
+    /// otherwise.
+    fn execute_as<BlobJson, ErrorJson: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint<BlobJson, ErrorJson>,
+        session_cookie: Option<&str>,
+    ) -> ApiResult<blocking::Response, ErrorJson> {
+        let url = endpoint.url(&self.base_urls)?;
+        let logged_url = logging::redacted(&url);
+        let method = &endpoint.method;
+        let mut retries = 0;
+        loop {
+            thread::sleep(self.rate_limiter.reserve());
+            let started = Instant::now();
+            let request = self.request(endpoint, url.clone(), session_cookie);
+            let (delay, result) = match request.send() {
+                Ok(resp) => {
+                    let latency = started.elapsed();
+                    logging::response(method, &logged_url, resp.status(), latency, retries);
+                    if session_cookie.is_some() {
+                        self.session.check(resp.status());
+                    }
+                    let failure = Failure::Status {
+                        status_code: resp.status(),
+                        headers: resp.headers(),
+                    };
+                    let delay = self.retry_policy.retry_delay(method, retries, failure);
+                    (delay, Ok(resp))
+                }
+                Err(err) => {
+                    let latency = started.elapsed();
+                    logging::network_error(method, &logged_url, &err, latency, retries);
+                    let failure = Failure::Network(&err);
+                    let delay = self.retry_policy.retry_delay(method, retries, failure);
+                    (delay, Err(ApiError::Network(err)))
+                }
+            };
+            match delay {
+                Some(delay) => {
+                    logging::retry(method, &logged_url, delay, retries);
+                    thread::sleep(delay);
+                }
+                None => return result,
+            }
+            retries += 1;
+        }
+    }
+
+    /// Send the request of `endpoint`, and parse its response.
+    pub(crate) fn send<BlobJson: DeserializeOwned, ErrorJson: DeserializeOwned>(
+        &self,
+        endpoint: EndpointResult<BlobJson, ErrorJson>,
+    ) -> ApiResult<BlobJson, ErrorJson> {
+        let resp = self.execute(&endpoint?)?;
+        let status_code = resp.status();
+        return parse_response(status_code, resp.text().ok());
+    }
+
+    /// Create a time entry. Look at `TimeEntry`'s documentation for fields that are required.
+    pub fn time_entry_create(
+        &self,
+        time_entry: &TimeEntry,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_create(time_entry));
+    }
+
+    /// Get a time entry by its id.
+    pub fn time_entry_get(
+        &self,
+        id: TimeEntryId,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_get(id));
+    }
+
+    /// Update a time entry. Only the fields that are set on `time_entry` are sent, but note that
+    /// `start` and `duration` are always sent.
+    pub fn time_entry_update(
+        &self,
+        id: TimeEntryId,
+        time_entry: &TimeEntry,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_update(id, time_entry));
+    }
+
+    /// Delete a time entry.
+    pub fn time_entry_delete(&self, id: TimeEntryId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::time_entry_delete(id)).map(|_| ());
+    }
+
+    /// Get the time entries started between `start_date` and `end_date`.
+    pub fn time_entries(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> ApiResult<Vec<TimeEntry>, DefaultErrorJson> {
+        return self.send(endpoints::time_entries(start_date, end_date));
+    }
+
+    /// Update several time entries at once, in requests of `BULK_CHUNK_SIZE` time entries. The
+    /// successes are the updated time entries. Toggl accepts or rejects a request as a whole, so a
+    /// failure holds the ids of its whole chunk: `failed_ids` lists every id that wasn't updated.
+    pub fn time_entries_bulk_update(
+        &self,
+        ids: &[TimeEntryId],
+        patch: &TimeEntryPatch,
+    ) -> BulkResult<TimeEntry> {
+        let mut result = BulkResult::new();
+        for chunk in ids.chunks(BULK_CHUNK_SIZE) {
+            let response = self.send(endpoints::time_entries_bulk_update(chunk, patch));
+            result.record(chunk, response.map(|response| response.data));
+        }
+        return result;
+    }
+
+    /// Delete several time entries at once, in requests of `BULK_CHUNK_SIZE` time entries. The
+    /// successes are the ids of the deleted time entries, and as with updates, a failure holds the
+    /// ids of its whole chunk.
+    pub fn time_entries_bulk_delete(&self, ids: &[TimeEntryId]) -> BulkResult<TimeEntryId> {
+        let mut result = BulkResult::new();
+        for chunk in ids.chunks(BULK_CHUNK_SIZE) {
+            let response = self.send(endpoints::time_entries_bulk_delete(chunk));
+            result.record(chunk, response.map(|_| chunk.to_vec()));
+        }
+        return result;
+    }
+
+    /// Start a time entry.
+    pub fn time_entry_start(
+        &self,
+        time_entry: &TimeEntryStart,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_start(time_entry));
+    }
+
+    /// Stop a running time entry.
+    pub fn time_entry_stop(
+        &self,
+        id: TimeEntryId,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_stop(id));
+    }
+
+    /// Get the currently running time entry, if any.
+    pub fn time_entry_current(&self) -> ApiResult<CurrentTimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_current());
+    }
+
+    /// Get workspaces
+    pub fn workspaces_get_all(&self) -> ApiResult<Vec<Workspace>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_get_all());
+    }
+
+    /// Get workspace tags
+    pub fn workspaces_tags_all(&self, wid: WorkspaceId) -> ApiResult<Vec<Tag>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_tags_all(wid));
+    }
+
+    /// Get workspace projects
+    pub fn workspaces_projects_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Vec<Project>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_projects_all(wid));
+    }
+
+    /// Create a project.
+    pub fn project_create(
+        &self,
+        project: &Project,
+    ) -> ApiResult<ProjectResponse, DefaultErrorJson> {
+        return self.send(endpoints::project_create(project));
+    }
+
+    /// Get a project by its id.
+    pub fn project_get(&self, id: ProjectId) -> ApiResult<ProjectResponse, DefaultErrorJson> {
+        return self.send(endpoints::project_get(id));
+    }
+
+    /// Update a project.
+    pub fn project_update(
+        &self,
+        id: ProjectId,
+        project: &Project,
+    ) -> ApiResult<ProjectResponse, DefaultErrorJson> {
+        return self.send(endpoints::project_update(id, project));
+    }
+
+    /// Delete a project.
+    pub fn project_delete(&self, id: ProjectId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::project_delete(id)).map(|_| ());
+    }
+
+    /// Archive several projects, one request per project. Keeps going when a project fails to be
+    /// archived: the successes are the archived projects, and the failures are about project ids.
+    pub fn projects_archive(&self, ids: &[ProjectId]) -> BulkResult<Project, ProjectId> {
+        let mut result = BulkResult::new();
+        for id in ids {
+            let response = self.send(endpoints::project_archive(*id));
+            result.record(&[*id], response.map(|response| vec![response.data]));
+        }
+        return result;
+    }
+
+    /// Create a tag.
+    pub fn tag_create(&self, tag: &Tag) -> ApiResult<TagResponse, DefaultErrorJson> {
+        return self.send(endpoints::tag_create(tag));
+    }
+
+    /// Update a tag. Only the name of a tag can be changed.
+    pub fn tag_update(&self, id: TagId, tag: &Tag) -> ApiResult<TagResponse, DefaultErrorJson> {
+        return self.send(endpoints::tag_update(id, tag));
+    }
+
+    /// Delete a tag. Toggl removes it from the time entries it was assigned to.
+    pub fn tag_delete(&self, id: TagId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::tag_delete(id)).map(|_| ());
+    }
+
+    /// Rename the tag `id` of the workspace `wid`. If the workspace already has a tag called
+    /// `new_name`, `TagRenameError::NameTaken` is returned with that tag: use `tag_merge` to merge
+    /// into it.
+    pub fn tag_rename(
+        &self,
+        id: TagId,
+        wid: WorkspaceId,
+        new_name: &str,
+    ) -> Result<Tag, TagRenameError> {
+        let tags = self.workspaces_tags_all(wid)?;
+        if let Some(taken) = other_tag_named(tags, id, new_name) {
+            return Err(TagRenameError::NameTaken(taken));
+        }
+
+        let renamed = Tag {
+            id: Some(id),
+            name: new_name.to_owned(),
+            wid,
+        };
+        let updated = self.tag_update(id, &renamed)?;
+        return Ok(updated.data);
+    }
+
+    /// Merge the tag `from` into the tag `into`, of the same workspace: the time entries tagged
+    /// with `from` and started after `since` (e.g. when the workspace was created) get tagged with
+    /// `into`. They are the ones the detailed reports of the workspace list (hence `user_agent`),
+    /// searched one calendar year at a time, so the further back `since` is, the longer this
+    /// takes. `from` is deleted once every time entry is retagged; if any of them can't be (e.g.
+    /// Toggl doesn't let the user update the time entries of others), an error is returned and
+    /// `from` is kept. Returns the time entries that were retagged. Merging a tag into itself does
+    /// nothing.
+    pub fn tag_merge(
+        &self,
+        from: TagId,
+        into: &Tag,
+        since: DateTime<Utc>,
+        user_agent: &str,
+    ) -> ApiResult<Vec<TimeEntry>, DefaultErrorJson> {
+        // Deleting `from` would delete `into`, and untag every time entry.
+        if into.id == Some(from) {
+            return Ok(vec![]);
+        }
+        let mut ids = vec![];
+        for params in tag_search_params(from, into.wid, user_agent, since, Utc::now()) {
+            for entry in self.reports_detailed_iter(params) {
+                ids.extend(id_to_retag(entry?, into));
+            }
+        }
+        let updated = self
+            .time_entries_bulk_update(&ids, &add_tag_patch(into))
+            .into_result()?;
+        self.tag_delete(from)?;
+        return Ok(updated);
+    }
+
+    /// Create a task.
+    pub fn task_create(&self, task: &Task) -> ApiResult<TaskResponse, DefaultErrorJson> {
+        return self.send(endpoints::task_create(task));
+    }
+
+    /// Get a task by its id.
+    pub fn task_get(&self, id: TaskId) -> ApiResult<TaskResponse, DefaultErrorJson> {
+        return self.send(endpoints::task_get(id));
+    }
+
+    /// Update a task.
+    pub fn task_update(
+        &self,
+        id: TaskId,
+        task: &Task,
+    ) -> ApiResult<TaskResponse, DefaultErrorJson> {
+        return self.send(endpoints::task_update(id, task));
+    }
+
+    /// Delete a task.
+    pub fn task_delete(&self, id: TaskId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::task_delete(id)).map(|_| ());
+    }
+
+    /// Get project tasks: the active ones, the done ones, or both. Toggl returns `null` instead of
+    /// an empty list when the project has no such tasks, hence the `Option`.
+    pub fn projects_tasks_all(
+        &self,
+        pid: ProjectId,
+        active: TasksActive,
+    ) -> ApiResult<Option<Vec<Task>>, DefaultErrorJson> {
+        return self.send(endpoints::projects_tasks_all(pid, active));
+    }
+
+    /// Get workspace tasks. Same as with `projects_tasks_all`, `None` means there are no tasks.
+    pub fn workspaces_tasks_all(
+        &self,
+        wid: WorkspaceId,
+        active: TasksActive,
+    ) -> ApiResult<Option<Vec<Task>>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_tasks_all(wid, active));
+    }
+
+    /// Get the names of the tasks of a workspace, done ones included, by task id. Useful to
+    /// resolve `TimeEntry::tid` and `ReportTimeEntry::tid`.
+    pub fn workspaces_task_names(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<HashMap<TaskId, String>, DefaultErrorJson> {
+        let tasks = self.workspaces_tasks_all(wid, TasksActive::Both)?;
+        return Ok(task_names(tasks));
+    }
+
+    /// Get workspace users
+    pub fn workspaces_users_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Vec<WorkspaceUser>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_users_all(wid));
+    }
+
+    /// Get workspace groups. Toggl returns `null` instead of an empty list when there are no
+    /// groups, hence the `Option`.
+    pub fn workspaces_groups_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Option<Vec<Group>>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_groups_all(wid));
+    }
+
+    /// Get the users of a project, with their rates and full names. Same as with
+    /// `workspaces_groups_all`, `None` means the project has no users.
+    pub fn projects_users_all(
+        &self,
+        pid: ProjectId,
+    ) -> ApiResult<Option<Vec<ProjectUser>>, DefaultErrorJson> {
+        return self.send(endpoints::projects_users_all(pid));
+    }
+
+    /// Get the dashboard of a workspace: the most active users, and what everyone has been
+    /// working on lately.
+    pub fn dashboard(&self, wid: WorkspaceId) -> ApiResult<Dashboard, DefaultErrorJson> {
+        return self.send(endpoints::dashboard(wid));
+    }
+
+    /// Get workspace clients. Toggl returns `null` instead of an empty list when the workspace
+    /// has no clients, hence the `Option`.
+    pub fn workspaces_clients_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Option<Vec<Client>>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_clients_all(wid));
+    }
+
+    /// Create a client.
+    pub fn client_create(&self, client: &Client) -> ApiResult<ClientResponse, DefaultErrorJson> {
+        return self.send(endpoints::client_create(client));
+    }
+
+    /// Get a client by its id.
+    pub fn client_get(&self, id: ClientId) -> ApiResult<ClientResponse, DefaultErrorJson> {
+        return self.send(endpoints::client_get(id));
+    }
+
+    /// Update a client.
+    pub fn client_update(
+        &self,
+        id: ClientId,
+        client: &Client,
+    ) -> ApiResult<ClientResponse, DefaultErrorJson> {
+        return self.send(endpoints::client_update(id, client));
+    }
+
+    /// Delete a client.
+    pub fn client_delete(&self, id: ClientId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::client_delete(id)).map(|_| ());
+    }
+
+    /// Get the projects of a client. Toggl returns `null` instead of an empty list when the client
+    /// has no projects, hence the `Option`.
+    pub fn client_projects(
+        &self,
+        id: ClientId,
+        active: ProjectsActive,
+    ) -> ApiResult<Option<Vec<Project>>, DefaultErrorJson> {
+        return self.send(endpoints::client_projects(id, active));
+    }
+
+    /// Get reports
+    pub fn reports_detailed(
+        &self,
+        params: &ReportsDetailedParams,
+    ) -> ApiResult<Report<ReportTimeEntry>, ReportsErrorJson> {
+        return self.send(endpoints::reports_detailed(params));
+    }
+
+    /// Iterate over the time entries of every page of a detailed report, starting at the page set
+    /// on `params`. Pages are fetched lazily, as the iterator is consumed.
+    pub fn reports_detailed_iter(&self, params: ReportsDetailedParams) -> ReportsDetailedIter<'_> {
+        return ReportsDetailedIter {
+            api: self,
+            pager: ReportsPager::new(params),
+        };
+    }
+
+    /// Fetch the time entries of every page of a detailed report, starting at the page set on
+    /// `params`.
+    pub fn reports_detailed_all(
+        &self,
+        params: ReportsDetailedParams,
+    ) -> ApiResult<Vec<ReportTimeEntry>, ReportsErrorJson> {
+        return self.reports_detailed_iter(params).collect();
+    }
+
+    /// Get a summary report
+    pub fn reports_summary(
+        &self,
+        params: &ReportsSummaryParams,
+    ) -> ApiResult<SummaryReport, ReportsErrorJson> {
+        return self.send(endpoints::reports_summary(params));
+    }
+
+    /// Get a weekly report
+    pub fn reports_weekly(
+        &self,
+        params: &ReportsWeeklyParams,
+    ) -> ApiResult<WeeklyReport, ReportsErrorJson> {
+        return self.send(endpoints::reports_weekly(params));
+    }
+
+    /// Export a report in `format`, writing the file to `sink`. Returns the number of bytes
+    /// written.
+    pub fn reports_export<Params: ReportsExportParams, Sink: Write>(
+        &self,
+        params: &Params,
+        format: ExportFormat,
+        sink: &mut Sink,
+    ) -> ApiResult<u64, ReportsErrorJson> {
+        let mut resp = self.execute(&endpoints::reports_export(params, format)?)?;
+        if !resp.status().is_success() {
+            let status_code = resp.status();
+            return Err(server_error(status_code, resp.text().ok()));
+        }
+        return io::copy(&mut resp, sink).map_err(ApiError::Io);
+    }
+
+    /// Export a report in `format` to the file at `path`. The report is downloaded next to it
+    /// first, so `path` is only created or replaced once the export succeeded.
+    pub fn reports_export_to_file<Params: ReportsExportParams, P: AsRef<Path>>(
+        &self,
+        params: &Params,
+        format: ExportFormat,
+        path: P,
+    ) -> ApiResult<u64, ReportsErrorJson> {
+        let partial_path = partial_export_path(path.as_ref());
+        let mut file = File::create(&partial_path).map_err(ApiError::Io)?;
+        let written = self
+            .reports_export(params, format, &mut file)
+            .and_then(|written| file.flush().map(|()| written).map_err(ApiError::Io));
+        drop(file);
+        return finish_export(&partial_path, path.as_ref(), written);
+    }
+
+    /// Get current user
     pub fn current_user(
         &self,
         since: Option<DateTime<Utc>>,
     ) -> ApiResult<UserResponse, DefaultErrorJson> {
-        let endpoint = API_URL.to_owned() + "/me";
-
-        // Add params if since is passed
-        let endpoint = match since {
-                Some(datetime) => Url::parse_with_params(
-                    &endpoint,
-                    vec![
-                        ("with_related_data", "true"),
-                        ("since", &datetime.timestamp().to_string()),
-                    ],
-                ).unwrap(),
-                None => Url::parse(&endpoint).unwrap(),
+        return self.send(endpoints::current_user(since));
+    }
+
+    /// Update the profile settings of the current user.
+    pub fn current_user_update(
+        &self,
+        user: &UserUpdate,
+    ) -> ApiResult<UserResponse, DefaultErrorJson> {
+        return self.send(endpoints::current_user_update(user));
+    }
+
+    /// Reset the API token of the current user. The old token stops working: the returned `Api`,
+    /// configured like this one, authenticates with the new token, which `credentials` gives.
+    pub fn reset_api_token(&self) -> ApiResult<Api, DefaultErrorJson> {
+        let token = self.send(endpoints::reset_api_token())?;
+        return Ok(Api {
+            credentials: Credentials::ApiToken(token),
+            session: Arc::default(),
+            ..self.clone()
+        });
+    }
+}
+
+/// Where to download an export to `path`, before it is moved there.
+fn partial_export_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".part");
+    return path.with_file_name(file_name);
+}
+
+/// Move the export downloaded to `partial_path` into `path` if it succeeded. Otherwise it is
+/// removed, and whatever was at `path` is left untouched.
+fn finish_export(
+    partial_path: &Path,
+    path: &Path,
+    written: ApiResult<u64, ReportsErrorJson>,
+) -> ApiResult<u64, ReportsErrorJson> {
+    if written.is_err() {
+        // The error of the export is more useful than that of the cleanup.
+        let _ = fs::remove_file(partial_path);
+        return written;
+    }
+    fs::rename(partial_path, path).map_err(ApiError::Io)?;
+    return written;
+}
+
+/// Another tag than `id` that is called `name`, if any.
+fn other_tag_named(tags: Vec<Tag>, id: TagId, name: &str) -> Option<Tag> {
+    return tags
+        .into_iter()
+        .find(|other| other.name == name && other.id != Some(id));
+}
+
+/// The params of the detailed reports that list the time entries of the workspace `wid` tagged
+/// with `tag`: one per calendar year, from `since` until the end of next year (time entries can be
+/// in the future), since reports can't span more than a year.
+fn tag_search_params(
+    tag: TagId,
+    wid: WorkspaceId,
+    user_agent: &str,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<ReportsDetailedParams> {
+    return (since.year()..=now.year() + 1)
+        .map(|year| {
+            let reports_params = ReportsParams {
+                since: Some(since.max(Utc.ymd(year, 1, 1).and_hms(0, 0, 0))),
+                until: Some(Utc.ymd(year, 12, 31).and_hms(0, 0, 0)),
+                tag_ids: Some(vec![tag]),
+                ..ReportsParams::new(user_agent.to_owned(), wid)
             };
+            return ReportsDetailedParams::with_params(reports_params, 1);
+        })
+        .collect();
+}
 
-        println!("Requesting: {}", endpoint);
-        let result = self.client.get(endpoint).add_api_key(self).get_json();
-        return result;
+/// The id of `entry`, if it isn't tagged with `into` yet.
+fn id_to_retag(entry: ReportTimeEntry, into: &Tag) -> Option<TimeEntryId> {
+    if entry.tags.contains(&into.name) {
+        return None;
+    }
+    return Some(entry.id);
+}
+
+/// The bulk update that adds `tag` to time entries, keeping their other tags.
+fn add_tag_patch(tag: &Tag) -> TimeEntryPatch {
+    return TimeEntryPatch {
+        tags: Some(vec![tag.name.clone()]),
+        tag_action: Some(TagAction::Add),
+        ..Default::default()
+    };
+}
+
+/// The names of `tasks`, by task id.
+fn task_names(tasks: Option<Vec<Task>>) -> HashMap<TaskId, String> {
+    return tasks
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|task| task.id.map(|id| (id, task.name)))
+        .collect();
+}
+
+/// Walks through the pages of a detailed report: holds the time entries of the current page, and
+/// the params to fetch the next one with. Fetching is left to the iterators.
+#[derive(Debug)]
+pub(crate) struct ReportsPager {
+    params: ReportsDetailedParams,
+    current_page: std::vec::IntoIter<ReportTimeEntry>,
+    done: bool,
+}
+
+impl ReportsPager {
+    pub(crate) fn new(params: ReportsDetailedParams) -> Self {
+        Self {
+            params,
+            current_page: vec![].into_iter(),
+            done: false,
+        }
+    }
+
+    /// The next time entry of the current page, if any is left.
+    pub(crate) fn pop(&mut self) -> Option<ReportTimeEntry> {
+        return self.current_page.next();
+    }
+
+    /// The params to fetch the next page with, or `None` if there are no more pages.
+    pub(crate) fn next_page_params(&self) -> Option<&ReportsDetailedParams> {
+        if self.done {
+            return None;
+        }
+        return Some(&self.params);
+    }
+
+    /// Take in the next page. Stops paging after an error.
+    pub(crate) fn receive(
+        &mut self,
+        report: ApiResult<Report<ReportTimeEntry>, ReportsErrorJson>,
+    ) -> ApiResult<(), ReportsErrorJson> {
+        let report = match report {
+            Ok(report) => report,
+            Err(err) => {
+                self.done = true;
+                return Err(err);
+            }
+        };
+        let page = self.params.page();
+        // An empty page means we went past the end, which can happen if entries are deleted
+        // while we're paging.
+        if report.data.is_empty() || page * report.per_page >= report.total_count {
+            self.done = true;
+        }
+        self.params.set_page(page + 1);
+        self.current_page = report.data.into_iter();
+        return Ok(());
+    }
+}
+
+/// Iterator over the time entries of a detailed report, that walks through the pages of the report.
+/// Yields an error (and then stops) if fetching a page fails.
+pub struct ReportsDetailedIter<'a> {
+    api: &'a Api,
+    pager: ReportsPager,
+}
+
+impl<'a> Iterator for ReportsDetailedIter<'a> {
+    type Item = ApiResult<ReportTimeEntry, ReportsErrorJson>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.pager.pop() {
+                return Some(Ok(entry));
+            }
+            let report = self.api.reports_detailed(self.pager.next_page_params()?);
+            if let Err(err) = self.pager.receive(report) {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn build(
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<ReportsParams, ReportsParamsError> {
+        return ReportsParams::builder("toggl_oxide".to_owned(), WorkspaceId(3))
+            .since(since)
+            .until(until)
+            .build();
+    }
+
+    #[test]
+    fn a_span_of_exactly_one_year_is_accepted() {
+        let result = build(
+            Utc.ymd(2021, 3, 1).and_hms(0, 0, 0),
+            Utc.ymd(2022, 3, 1).and_hms(23, 0, 0),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn a_span_of_one_year_and_a_day_is_rejected() {
+        let result = build(
+            Utc.ymd(2021, 3, 1).and_hms(0, 0, 0),
+            Utc.ymd(2022, 3, 2).and_hms(0, 0, 0),
+        );
+        assert!(
+            matches!(result, Err(ReportsParamsError::SpanTooLong { .. })),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn a_year_after_feb_29_is_feb_28() {
+        assert_eq!(
+            one_year_after(NaiveDate::from_ymd(2020, 2, 29)),
+            NaiveDate::from_ymd(2021, 2, 28)
+        );
+
+        let since = Utc.ymd(2020, 2, 29).and_hms(0, 0, 0);
+        let result = build(since, Utc.ymd(2021, 2, 28).and_hms(0, 0, 0));
+        assert!(result.is_ok(), "{:?}", result);
+        let result = build(since, Utc.ymd(2021, 3, 1).and_hms(0, 0, 0));
+        assert!(
+            matches!(result, Err(ReportsParamsError::SpanTooLong { .. })),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn until_before_since_is_rejected() {
+        let result = build(
+            Utc.ymd(2021, 3, 2).and_hms(0, 0, 0),
+            Utc.ymd(2021, 3, 1).and_hms(0, 0, 0),
+        );
+        assert!(
+            matches!(result, Err(ReportsParamsError::UntilBeforeSince { .. })),
+            "{:?}",
+            result
+        );
     }
 }
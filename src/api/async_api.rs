@@ -0,0 +1,729 @@
+//! The async counterpart of `Api`, enabled with the `async` feature. Requests are built by
+//! `endpoints` and responses parsed by `parse_response`, same as with the blocking `Api`, so both
+//! return the same results and errors.
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use super::*;
+
+impl AddApiKey for reqwest::RequestBuilder {
+    fn add_api_key(self, credentials: &Credentials) -> Self {
+        let (username, password) = credentials.basic_auth();
+        return self.basic_auth(username, Some(password));
+    }
+}
+
+/// The main Api object, for use with async code. Has the same methods as `Api`, and is as cheap
+/// to clone.
+#[derive(Clone)]
+pub struct AsyncApi {
+    credentials: Credentials,
+    client: reqwest::Client,
+    base_urls: BaseUrls,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    session: Arc<Session>,
+}
+
+impl AsyncApi {
+    pub fn new(api_key: &str) -> AsyncApi {
+        return Self::with_credentials(Credentials::ApiToken(api_key.to_owned()));
+    }
+
+    /// Log in with the email and password of the user. See `Api::login`.
+    pub fn login(email: &str, password: &str) -> AsyncApi {
+        return Self::with_credentials(Credentials::Login {
+            email: email.to_owned(),
+            password: password.to_owned(),
+        });
+    }
+
+    pub fn with_credentials(credentials: Credentials) -> AsyncApi {
+        return Self::from_parts(
+            credentials,
+            reqwest::Client::new(),
+            BaseUrls::default(),
+            Arc::new(RateLimiter::new(REQUEST_INTERVAL)),
+            RetryPolicy::default(),
+        );
+    }
+
+    /// Authenticate with the credentials in the env. See `Credentials::from_env`.
+    pub fn from_env() -> Result<AsyncApi, ConfigError> {
+        return Ok(Self::with_credentials(Credentials::from_env()?));
+    }
+
+    /// Authenticate with the credentials in a config file. See `Credentials::from_config_file`.
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<AsyncApi, ConfigError> {
+        return Ok(Self::with_credentials(Credentials::from_config_file(path)?));
+    }
+
+    pub(crate) fn from_parts(
+        credentials: Credentials,
+        client: reqwest::Client,
+        base_urls: BaseUrls,
+        rate_limiter: Arc<RateLimiter>,
+        retry_policy: RetryPolicy,
+    ) -> AsyncApi {
+        AsyncApi {
+            credentials,
+            client,
+            base_urls,
+            rate_limiter,
+            retry_policy,
+            session: Arc::default(),
+        }
+    }
+
+    /// What the requests are authenticated with.
+    pub fn credentials(&self) -> &Credentials {
+        return &self.credentials;
+    }
+
+    /// Start building an `AsyncApi` with other base URLs, timeouts, ... Finish with
+    /// `ApiBuilder::build_async`.
+    pub fn builder(api_key: &str) -> ApiBuilder {
+        return Api::builder(api_key);
+    }
+
+    /// Build the request of `endpoint` to `url`, authenticated with the session cookie if there
+    /// is one, and with the credentials otherwise.
+    fn request<BlobJson, ErrorJson>(
+        &self,
+        endpoint: &Endpoint<BlobJson, ErrorJson>,
+        url: Url,
+        session_cookie: Option<&str>,
+    ) -> reqwest::RequestBuilder {
+        let mut request = self.client.request(endpoint.method.clone(), url);
+        request = match session_cookie {
+            Some(cookie) => request.header(COOKIE, cookie),
+            None => request.add_api_key(&self.credentials),
+        };
+        if let Some(body) = &endpoint.body {
+            request = request.json(body);
+        }
+        return request;
+    }
+
+    /// Send the request of `endpoint` when the rate limiter allows it, retrying as per the retry
+    /// policy. Returns the response, whatever its status. Same as with `Api`, an expired session
+    /// is opened again once.
+    async fn execute<BlobJson, ErrorJson: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint<BlobJson, ErrorJson>,
+    ) -> ApiResult<reqwest::Response, ErrorJson> {
+        let session_cookie = self.session_cookie().await?;
+        let resp = self.execute_as(endpoint, session_cookie.as_deref()).await?;
+        if session_cookie.is_none() || !session::is_refused(resp.status()) {
+            return Ok(resp);
+        }
+        let session_cookie = self.session_cookie().await?;
+        return self.execute_as(endpoint, session_cookie.as_deref()).await;
+    }
+
+    /// The cookie of the session to send instead of the credentials, opening the session first if
+    /// there is none yet. `None` for API tokens, which are sent as they are.
+    async fn session_cookie<ErrorJson: DeserializeOwned>(
+        &self,
+    ) -> ApiResult<Option<String>, ErrorJson> {
+        if let Credentials::ApiToken(_) = self.credentials {
+            return Ok(None);
+        }
+        if let Some(cookie) = self.session.cookie() {
+            return Ok(Some(cookie));
+        }
+        // Concurrent requests may open a session each: the last one is kept.
+        let resp = self
+            .execute_as(&endpoints::session_create(ApiVersion::V8)?, None)
+            .await?;
+        let status_code = resp.status();
+        if status_code.is_success() {
+            if let Some(cookie) = self.session.open(resp.headers()) {
+                return Ok(Some(cookie));
+            }
+        }
+        return Err(server_error(status_code, resp.text().await.ok()));
+    }
+
+    /// Same as `execute`, authenticated with `session_cookie` if set, and with the credentials
+    /// otherwise.
+    async fn execute_as<BlobJson, ErrorJson: DeserializeOwned>(
+        &self,
+        endpoint: &Endpoint<BlobJson, ErrorJson>,
+        session_cookie: Option<&str>,
+    ) -> ApiResult<reqwest::Response, ErrorJson> {
+        let url = endpoint.url(&self.base_urls)?;
+        let logged_url = logging::redacted(&url);
+        let method = &endpoint.method;
+        let mut retries = 0;
+        loop {
+            tokio::time::sleep(self.rate_limiter.reserve()).await;
+            let started = Instant::now();
+            let request = self.request(endpoint, url.clone(), session_cookie);
+            let (delay, result) = match request.send().await {
+                Ok(resp) => {
+                    let latency = started.elapsed();
+                    logging::response(method, &logged_url, resp.status(), latency, retries);
+                    if session_cookie.is_some() {
+                        self.session.check(resp.status());
+                    }
+                    let failure = Failure::Status {
+                        status_code: resp.status(),
+                        headers: resp.headers(),
+                    };
+                    let delay = self.retry_policy.retry_delay(method, retries, failure);
+                    (delay, Ok(resp))
+                }
+                Err(err) => {
+                    let latency = started.elapsed();
+                    logging::network_error(method, &logged_url, &err, latency, retries);
+                    let failure = Failure::Network(&err);
+                    let delay = self.retry_policy.retry_delay(method, retries, failure);
+                    (delay, Err(ApiError::Network(err)))
+                }
+            };
+            match delay {
+                Some(delay) => {
+                    logging::retry(method, &logged_url, delay, retries);
+                    tokio::time::sleep(delay).await;
+                }
+                None => return result,
+            }
+            retries += 1;
+        }
+    }
+
+    /// Send the request of `endpoint`, and parse its response.
+    async fn send<BlobJson: DeserializeOwned, ErrorJson: DeserializeOwned>(
+        &self,
+        endpoint: EndpointResult<BlobJson, ErrorJson>,
+    ) -> ApiResult<BlobJson, ErrorJson> {
+        let resp = self.execute(&endpoint?).await?;
+        let status_code = resp.status();
+        return parse_response(status_code, resp.text().await.ok());
+    }
+
+    /// Create a time entry. Look at `TimeEntry`'s documentation for fields that are required.
+    pub async fn time_entry_create(
+        &self,
+        time_entry: &TimeEntry,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_create(time_entry)).await;
+    }
+
+    /// Get a time entry by its id.
+    pub async fn time_entry_get(
+        &self,
+        id: TimeEntryId,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_get(id)).await;
+    }
+
+    /// Update a time entry. Only the fields that are set on `time_entry` are sent, but note that
+    /// `start` and `duration` are always sent.
+    pub async fn time_entry_update(
+        &self,
+        id: TimeEntryId,
+        time_entry: &TimeEntry,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self
+            .send(endpoints::time_entry_update(id, time_entry))
+            .await;
+    }
+
+    /// Delete a time entry.
+    pub async fn time_entry_delete(&self, id: TimeEntryId) -> ApiResult<(), DefaultErrorJson> {
+        return self
+            .send(endpoints::time_entry_delete(id))
+            .await
+            .map(|_| ());
+    }
+
+    /// Get the time entries started between `start_date` and `end_date`.
+    pub async fn time_entries(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> ApiResult<Vec<TimeEntry>, DefaultErrorJson> {
+        return self
+            .send(endpoints::time_entries(start_date, end_date))
+            .await;
+    }
+
+    /// Update several time entries at once, in requests of `BULK_CHUNK_SIZE` time entries. The
+    /// successes are the updated time entries. Toggl accepts or rejects a request as a whole, so a
+    /// failure holds the ids of its whole chunk: `failed_ids` lists every id that wasn't updated.
+    pub async fn time_entries_bulk_update(
+        &self,
+        ids: &[TimeEntryId],
+        patch: &TimeEntryPatch,
+    ) -> BulkResult<TimeEntry> {
+        let mut result = BulkResult::new();
+        for chunk in ids.chunks(BULK_CHUNK_SIZE) {
+            let response = self
+                .send(endpoints::time_entries_bulk_update(chunk, patch))
+                .await;
+            result.record(chunk, response.map(|response| response.data));
+        }
+        return result;
+    }
+
+    /// Delete several time entries at once, in requests of `BULK_CHUNK_SIZE` time entries. The
+    /// successes are the ids of the deleted time entries, and as with updates, a failure holds the
+    /// ids of its whole chunk.
+    pub async fn time_entries_bulk_delete(&self, ids: &[TimeEntryId]) -> BulkResult<TimeEntryId> {
+        let mut result = BulkResult::new();
+        for chunk in ids.chunks(BULK_CHUNK_SIZE) {
+            let response = self.send(endpoints::time_entries_bulk_delete(chunk)).await;
+            result.record(chunk, response.map(|_| chunk.to_vec()));
+        }
+        return result;
+    }
+
+    /// Start a time entry.
+    pub async fn time_entry_start(
+        &self,
+        time_entry: &TimeEntryStart,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_start(time_entry)).await;
+    }
+
+    /// Stop a running time entry.
+    pub async fn time_entry_stop(
+        &self,
+        id: TimeEntryId,
+    ) -> ApiResult<TimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_stop(id)).await;
+    }
+
+    /// Get the currently running time entry, if any.
+    pub async fn time_entry_current(
+        &self,
+    ) -> ApiResult<CurrentTimeEntryResponse, DefaultErrorJson> {
+        return self.send(endpoints::time_entry_current()).await;
+    }
+
+    /// Get workspaces
+    pub async fn workspaces_get_all(&self) -> ApiResult<Vec<Workspace>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_get_all()).await;
+    }
+
+    /// Get workspace tags
+    pub async fn workspaces_tags_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Vec<Tag>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_tags_all(wid)).await;
+    }
+
+    /// Get workspace projects
+    pub async fn workspaces_projects_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Vec<Project>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_projects_all(wid)).await;
+    }
+
+    /// Create a project.
+    pub async fn project_create(
+        &self,
+        project: &Project,
+    ) -> ApiResult<ProjectResponse, DefaultErrorJson> {
+        return self.send(endpoints::project_create(project)).await;
+    }
+
+    /// Get a project by its id.
+    pub async fn project_get(&self, id: ProjectId) -> ApiResult<ProjectResponse, DefaultErrorJson> {
+        return self.send(endpoints::project_get(id)).await;
+    }
+
+    /// Update a project.
+    pub async fn project_update(
+        &self,
+        id: ProjectId,
+        project: &Project,
+    ) -> ApiResult<ProjectResponse, DefaultErrorJson> {
+        return self.send(endpoints::project_update(id, project)).await;
+    }
+
+    /// Delete a project.
+    pub async fn project_delete(&self, id: ProjectId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::project_delete(id)).await.map(|_| ());
+    }
+
+    /// Archive several projects, one request per project. Keeps going when a project fails to be
+    /// archived: the successes are the archived projects, and the failures are about project ids.
+    pub async fn projects_archive(&self, ids: &[ProjectId]) -> BulkResult<Project, ProjectId> {
+        let mut result = BulkResult::new();
+        for id in ids {
+            let response = self.send(endpoints::project_archive(*id)).await;
+            result.record(&[*id], response.map(|response| vec![response.data]));
+        }
+        return result;
+    }
+
+    /// Create a tag.
+    pub async fn tag_create(&self, tag: &Tag) -> ApiResult<TagResponse, DefaultErrorJson> {
+        return self.send(endpoints::tag_create(tag)).await;
+    }
+
+    /// Update a tag. Only the name of a tag can be changed.
+    pub async fn tag_update(
+        &self,
+        id: TagId,
+        tag: &Tag,
+    ) -> ApiResult<TagResponse, DefaultErrorJson> {
+        return self.send(endpoints::tag_update(id, tag)).await;
+    }
+
+    /// Delete a tag. Toggl removes it from the time entries it was assigned to.
+    pub async fn tag_delete(&self, id: TagId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::tag_delete(id)).await.map(|_| ());
+    }
+
+    /// Rename the tag `id` of the workspace `wid`. See `Api::tag_rename`.
+    pub async fn tag_rename(
+        &self,
+        id: TagId,
+        wid: WorkspaceId,
+        new_name: &str,
+    ) -> Result<Tag, TagRenameError> {
+        let tags = self.workspaces_tags_all(wid).await?;
+        if let Some(taken) = other_tag_named(tags, id, new_name) {
+            return Err(TagRenameError::NameTaken(taken));
+        }
+
+        let renamed = Tag {
+            id: Some(id),
+            name: new_name.to_owned(),
+            wid,
+        };
+        let updated = self.tag_update(id, &renamed).await?;
+        return Ok(updated.data);
+    }
+
+    /// Merge the tag `from` into the tag `into`, of the same workspace. See `Api::tag_merge`.
+    pub async fn tag_merge(
+        &self,
+        from: TagId,
+        into: &Tag,
+        since: DateTime<Utc>,
+        user_agent: &str,
+    ) -> ApiResult<Vec<TimeEntry>, DefaultErrorJson> {
+        // Deleting `from` would delete `into`, and untag every time entry.
+        if into.id == Some(from) {
+            return Ok(vec![]);
+        }
+        let mut ids = vec![];
+        for params in tag_search_params(from, into.wid, user_agent, since, Utc::now()) {
+            let mut iter = self.reports_detailed_iter(params);
+            while let Some(entry) = iter.next().await {
+                ids.extend(id_to_retag(entry?, into));
+            }
+        }
+        let updated = self
+            .time_entries_bulk_update(&ids, &add_tag_patch(into))
+            .await
+            .into_result()?;
+        self.tag_delete(from).await?;
+        return Ok(updated);
+    }
+
+    /// Create a task.
+    pub async fn task_create(&self, task: &Task) -> ApiResult<TaskResponse, DefaultErrorJson> {
+        return self.send(endpoints::task_create(task)).await;
+    }
+
+    /// Get a task by its id.
+    pub async fn task_get(&self, id: TaskId) -> ApiResult<TaskResponse, DefaultErrorJson> {
+        return self.send(endpoints::task_get(id)).await;
+    }
+
+    /// Update a task.
+    pub async fn task_update(
+        &self,
+        id: TaskId,
+        task: &Task,
+    ) -> ApiResult<TaskResponse, DefaultErrorJson> {
+        return self.send(endpoints::task_update(id, task)).await;
+    }
+
+    /// Delete a task.
+    pub async fn task_delete(&self, id: TaskId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::task_delete(id)).await.map(|_| ());
+    }
+
+    /// Get project tasks: the active ones, the done ones, or both. Toggl returns `null` instead of
+    /// an empty list when the project has no such tasks, hence the `Option`.
+    pub async fn projects_tasks_all(
+        &self,
+        pid: ProjectId,
+        active: TasksActive,
+    ) -> ApiResult<Option<Vec<Task>>, DefaultErrorJson> {
+        return self.send(endpoints::projects_tasks_all(pid, active)).await;
+    }
+
+    /// Get workspace tasks. Same as with `projects_tasks_all`, `None` means there are no tasks.
+    pub async fn workspaces_tasks_all(
+        &self,
+        wid: WorkspaceId,
+        active: TasksActive,
+    ) -> ApiResult<Option<Vec<Task>>, DefaultErrorJson> {
+        return self
+            .send(endpoints::workspaces_tasks_all(wid, active))
+            .await;
+    }
+
+    /// Get the names of the tasks of a workspace, done ones included, by task id. Useful to
+    /// resolve `TimeEntry::tid` and `ReportTimeEntry::tid`.
+    pub async fn workspaces_task_names(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<HashMap<TaskId, String>, DefaultErrorJson> {
+        let tasks = self.workspaces_tasks_all(wid, TasksActive::Both).await?;
+        return Ok(task_names(tasks));
+    }
+
+    /// Get workspace users
+    pub async fn workspaces_users_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Vec<WorkspaceUser>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_users_all(wid)).await;
+    }
+
+    /// Get workspace groups. Toggl returns `null` instead of an empty list when there are no
+    /// groups, hence the `Option`.
+    pub async fn workspaces_groups_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Option<Vec<Group>>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_groups_all(wid)).await;
+    }
+
+    /// Get the users of a project, with their rates and full names. Same as with
+    /// `workspaces_groups_all`, `None` means the project has no users.
+    pub async fn projects_users_all(
+        &self,
+        pid: ProjectId,
+    ) -> ApiResult<Option<Vec<ProjectUser>>, DefaultErrorJson> {
+        return self.send(endpoints::projects_users_all(pid)).await;
+    }
+
+    /// Get the dashboard of a workspace: the most active users, and what everyone has been
+    /// working on lately.
+    pub async fn dashboard(&self, wid: WorkspaceId) -> ApiResult<Dashboard, DefaultErrorJson> {
+        return self.send(endpoints::dashboard(wid)).await;
+    }
+
+    /// Get workspace clients. Toggl returns `null` instead of an empty list when the workspace
+    /// has no clients, hence the `Option`.
+    pub async fn workspaces_clients_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Option<Vec<Client>>, DefaultErrorJson> {
+        return self.send(endpoints::workspaces_clients_all(wid)).await;
+    }
+
+    /// Create a client.
+    pub async fn client_create(
+        &self,
+        client: &Client,
+    ) -> ApiResult<ClientResponse, DefaultErrorJson> {
+        return self.send(endpoints::client_create(client)).await;
+    }
+
+    /// Get a client by its id.
+    pub async fn client_get(&self, id: ClientId) -> ApiResult<ClientResponse, DefaultErrorJson> {
+        return self.send(endpoints::client_get(id)).await;
+    }
+
+    /// Update a client.
+    pub async fn client_update(
+        &self,
+        id: ClientId,
+        client: &Client,
+    ) -> ApiResult<ClientResponse, DefaultErrorJson> {
+        return self.send(endpoints::client_update(id, client)).await;
+    }
+
+    /// Delete a client.
+    pub async fn client_delete(&self, id: ClientId) -> ApiResult<(), DefaultErrorJson> {
+        return self.send(endpoints::client_delete(id)).await.map(|_| ());
+    }
+
+    /// Get the projects of a client. Toggl returns `null` instead of an empty list when the client
+    /// has no projects, hence the `Option`.
+    pub async fn client_projects(
+        &self,
+        id: ClientId,
+        active: ProjectsActive,
+    ) -> ApiResult<Option<Vec<Project>>, DefaultErrorJson> {
+        return self.send(endpoints::client_projects(id, active)).await;
+    }
+
+    /// Get reports
+    pub async fn reports_detailed(
+        &self,
+        params: &ReportsDetailedParams,
+    ) -> ApiResult<Report<ReportTimeEntry>, ReportsErrorJson> {
+        return self.send(endpoints::reports_detailed(params)).await;
+    }
+
+    /// Iterate over the time entries of every page of a detailed report, starting at the page set
+    /// on `params`. Pages are fetched lazily, as the iterator is consumed.
+    pub fn reports_detailed_iter(
+        &self,
+        params: ReportsDetailedParams,
+    ) -> AsyncReportsDetailedIter<'_> {
+        return AsyncReportsDetailedIter {
+            api: self,
+            pager: ReportsPager::new(params),
+        };
+    }
+
+    /// Fetch the time entries of every page of a detailed report, starting at the page set on
+    /// `params`.
+    pub async fn reports_detailed_all(
+        &self,
+        params: ReportsDetailedParams,
+    ) -> ApiResult<Vec<ReportTimeEntry>, ReportsErrorJson> {
+        let mut iter = self.reports_detailed_iter(params);
+        let mut entries = vec![];
+        while let Some(entry) = iter.next().await {
+            entries.push(entry?);
+        }
+        return Ok(entries);
+    }
+
+    /// Get a summary report
+    pub async fn reports_summary(
+        &self,
+        params: &ReportsSummaryParams,
+    ) -> ApiResult<SummaryReport, ReportsErrorJson> {
+        return self.send(endpoints::reports_summary(params)).await;
+    }
+
+    /// Get a weekly report
+    pub async fn reports_weekly(
+        &self,
+        params: &ReportsWeeklyParams,
+    ) -> ApiResult<WeeklyReport, ReportsErrorJson> {
+        return self.send(endpoints::reports_weekly(params)).await;
+    }
+
+    /// Export a report in `format`, writing the file to `sink` as it is downloaded. Returns the
+    /// number of bytes written.
+    pub async fn reports_export<Params: ReportsExportParams, Sink: AsyncWrite + Unpin>(
+        &self,
+        params: &Params,
+        format: ExportFormat,
+        sink: &mut Sink,
+    ) -> ApiResult<u64, ReportsErrorJson> {
+        let endpoint = endpoints::reports_export(params, format)?;
+        let mut resp = self.execute(&endpoint).await?;
+        if !resp.status().is_success() {
+            let status_code = resp.status();
+            return Err(server_error(status_code, resp.text().await.ok()));
+        }
+        let mut written = 0;
+        while let Some(chunk) = resp.chunk().await.map_err(ApiError::Network)? {
+            sink.write_all(&chunk).await.map_err(ApiError::Io)?;
+            written += chunk.len() as u64;
+        }
+        return Ok(written);
+    }
+
+    /// Export a report in `format` to the file at `path`. The report is downloaded next to it
+    /// first, so `path` is only created or replaced once the export succeeded.
+    pub async fn reports_export_to_file<Params: ReportsExportParams, P: AsRef<Path>>(
+        &self,
+        params: &Params,
+        format: ExportFormat,
+        path: P,
+    ) -> ApiResult<u64, ReportsErrorJson> {
+        let partial_path = partial_export_path(path.as_ref());
+        let mut file = tokio::fs::File::create(&partial_path)
+            .await
+            .map_err(ApiError::Io)?;
+        let mut written = self.reports_export(params, format, &mut file).await;
+        if written.is_ok() {
+            if let Err(err) = file.flush().await {
+                written = Err(ApiError::Io(err));
+            }
+        }
+        drop(file);
+        return finish_async_export(&partial_path, path.as_ref(), written).await;
+    }
+
+    /// Get current user
+    pub async fn current_user(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> ApiResult<UserResponse, DefaultErrorJson> {
+        return self.send(endpoints::current_user(since)).await;
+    }
+
+    /// Update the profile settings of the current user.
+    pub async fn current_user_update(
+        &self,
+        user: &UserUpdate,
+    ) -> ApiResult<UserResponse, DefaultErrorJson> {
+        return self.send(endpoints::current_user_update(user)).await;
+    }
+
+    /// Reset the API token of the current user. See `Api::reset_api_token`.
+    pub async fn reset_api_token(&self) -> ApiResult<AsyncApi, DefaultErrorJson> {
+        let token = self.send(endpoints::reset_api_token()).await?;
+        return Ok(AsyncApi {
+            credentials: Credentials::ApiToken(token),
+            session: Arc::default(),
+            ..self.clone()
+        });
+    }
+}
+
+/// The async counterpart of `ReportsDetailedIter`. Call `next` until it returns `None`.
+pub struct AsyncReportsDetailedIter<'a> {
+    api: &'a AsyncApi,
+    pager: ReportsPager,
+}
+
+impl<'a> AsyncReportsDetailedIter<'a> {
+    /// The next time entry of the report, fetching the next page if needed. Yields an error (and
+    /// then stops) if fetching a page fails.
+    pub async fn next(&mut self) -> Option<ApiResult<ReportTimeEntry, ReportsErrorJson>> {
+        loop {
+            if let Some(entry) = self.pager.pop() {
+                return Some(Ok(entry));
+            }
+            let report = self
+                .api
+                .reports_detailed(self.pager.next_page_params()?)
+                .await;
+            if let Err(err) = self.pager.receive(report) {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// Same as `finish_export`, with tokio's file system operations.
+async fn finish_async_export(
+    partial_path: &Path,
+    path: &Path,
+    written: ApiResult<u64, ReportsErrorJson>,
+) -> ApiResult<u64, ReportsErrorJson> {
+    if written.is_err() {
+        // The error of the export is more useful than that of the cleanup.
+        let _ = tokio::fs::remove_file(partial_path).await;
+        return written;
+    }
+    tokio::fs::rename(partial_path, path)
+        .await
+        .map_err(ApiError::Io)?;
+    return written;
+}
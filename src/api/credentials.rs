@@ -0,0 +1,136 @@
+//! What requests are authenticated with: an API token, or the email and password of the user.
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/authentication.md
+use std::collections::HashMap;
+use std::env;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The env var (or config file entry) holding the API token.
+const API_KEY_VAR: &str = "TOGGL_API_KEY";
+
+/// The env var (or config file entry) holding the email, used when there's no API token.
+const EMAIL_VAR: &str = "TOGGL_EMAIL";
+
+/// The env var (or config file entry) holding the password that goes with the email.
+const PASSWORD_VAR: &str = "TOGGL_PASSWORD";
+
+/// How requests are authenticated. API tokens are sent with HTTP basic auth on every request. An
+/// email and password are only sent to open a session, whose cookie is sent instead.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// The API token of the user, found in their profile settings
+    ApiToken(String),
+
+    /// Log in with the email and password of the user, opening a session on the first request
+    Login { email: String, password: String },
+}
+
+/// Errors that can happen when reading credentials from env vars or a config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Neither TOGGL_API_KEY, nor TOGGL_EMAIL and TOGGL_PASSWORD, are set
+    MissingCredentials,
+
+    /// The config file couldn't be read
+    File(io::Error),
+
+    /// A line of the config file isn't a `NAME=value` entry, a comment or blank
+    Syntax { line: usize },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ConfigError::MissingCredentials => write!(
+                f,
+                "Neither {}, nor {} and {}, are set",
+                API_KEY_VAR, EMAIL_VAR, PASSWORD_VAR
+            ),
+            ConfigError::File(err) => write!(f, "Couldn't read the config file: {}", err),
+            ConfigError::Syntax { line } => {
+                write!(f, "Line {} of the config file isn't NAME=value", line)
+            }
+        };
+    }
+}
+
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return match self {
+            ConfigError::MissingCredentials | ConfigError::Syntax { .. } => None,
+            ConfigError::File(err) => Some(err),
+        };
+    }
+}
+
+// Secrets are kept out of logs and panic messages.
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Credentials::ApiToken(_) => f.debug_tuple("ApiToken").field(&"REDACTED").finish(),
+            Credentials::Login { email, .. } => f
+                .debug_struct("Login")
+                .field("email", email)
+                .field("password", &"REDACTED")
+                .finish(),
+        };
+    }
+}
+
+impl Credentials {
+    /// Read the credentials from TOGGL_API_KEY, or from TOGGL_EMAIL and TOGGL_PASSWORD.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        return Self::from_vars(|name| env::var(name).ok());
+    }
+
+    /// Read the credentials from a config file in the .env format, with the same entries as
+    /// `from_env`, e.g. `TOGGL_API_KEY=...`. The environment itself is left untouched.
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let entries = parse_config(&fs::read_to_string(path).map_err(ConfigError::File)?)?;
+        return Self::from_vars(|name| entries.get(name).cloned());
+    }
+
+    fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> Result<Self, ConfigError> {
+        if let Some(api_token) = var(API_KEY_VAR) {
+            return Ok(Credentials::ApiToken(api_token));
+        }
+        return match (var(EMAIL_VAR), var(PASSWORD_VAR)) {
+            (Some(email), Some(password)) => Ok(Credentials::Login { email, password }),
+            _ => Err(ConfigError::MissingCredentials),
+        };
+    }
+
+    /// The username and password to send with HTTP basic auth. Toggl expects API tokens as the
+    /// username, with "api_token" as password. Logins only send them to open their session.
+    pub(crate) fn basic_auth(&self) -> (&str, &str) {
+        return match self {
+            Credentials::ApiToken(api_token) => (api_token, "api_token"),
+            Credentials::Login { email, password } => (email, password),
+        };
+    }
+}
+
+/// The entries of a config file in the .env format: `NAME=value` lines, optionally prefixed with
+/// `export` and with the value in quotes, between comments and blank lines.
+fn parse_config(text: &str) -> Result<HashMap<String, String>, ConfigError> {
+    let mut entries = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => (name.trim(), value.trim()),
+            _ => return Err(ConfigError::Syntax { line: index + 1 }),
+        };
+        let unquoted = ['"', '\'']
+            .iter()
+            .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote));
+        entries.insert(name.to_owned(), unquoted.unwrap_or(value).to_owned());
+    }
+    return Ok(entries);
+}
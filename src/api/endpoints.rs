@@ -0,0 +1,449 @@
+//! The requests of every endpoint, independently of how they are sent. Both the blocking `Api`
+//! and the async one build their requests here, so that they only differ in how they send them.
+use std::marker::PhantomData;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Method, Url};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::Serialize;
+
+use super::*;
+
+/// Which of Toggl's APIs an endpoint belongs to.
+#[derive(Debug, Clone, Copy)]
+enum Base {
+    Track,
+    TrackV9,
+    Reports,
+}
+
+/// The base URLs of Toggl's APIs, which can be changed to send requests elsewhere (a local mock
+/// server, a proxy, ...).
+#[derive(Debug, Clone)]
+pub(crate) struct BaseUrls {
+    pub(crate) track: String,
+    pub(crate) track_v9: String,
+    pub(crate) reports: String,
+}
+
+impl Default for BaseUrls {
+    fn default() -> Self {
+        Self {
+            track: API_URL.to_owned(),
+            track_v9: V9_API_URL.to_owned(),
+            reports: REPORTS_API_URL.to_owned(),
+        }
+    }
+}
+
+impl BaseUrls {
+    fn of(&self, base: Base) -> &str {
+        return match base {
+            Base::Track => &self.track,
+            Base::TrackV9 => &self.track_v9,
+            Base::Reports => &self.reports,
+        };
+    }
+}
+
+/// A request to an endpoint, whose response is parsed into `BlobJson` (or `ErrorJson`).
+#[derive(Debug)]
+pub(crate) struct Endpoint<BlobJson, ErrorJson> {
+    pub(crate) method: Method,
+    base: Base,
+    pub(crate) path: String,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) body: Option<serde_json::Value>,
+    shape: PhantomData<fn() -> (BlobJson, ErrorJson)>,
+}
+
+/// Building an endpoint fails if its params or body can't be serialized.
+pub(crate) type EndpointResult<BlobJson, ErrorJson> =
+    ApiResult<Endpoint<BlobJson, ErrorJson>, ErrorJson>;
+
+impl<BlobJson, ErrorJson: DeserializeOwned> Endpoint<BlobJson, ErrorJson> {
+    fn new(method: Method, base: Base, path: String) -> Self {
+        Self {
+            method,
+            base,
+            path,
+            query: vec![],
+            body: None,
+            shape: PhantomData,
+        }
+    }
+
+    fn get(path: String) -> Self {
+        return Self::new(Method::GET, Base::Track, path);
+    }
+
+    fn post(path: String) -> Self {
+        return Self::new(Method::POST, Base::Track, path);
+    }
+
+    fn put(path: String) -> Self {
+        return Self::new(Method::PUT, Base::Track, path);
+    }
+
+    fn delete(path: String) -> Self {
+        return Self::new(Method::DELETE, Base::Track, path);
+    }
+
+    fn reports(path: String) -> Self {
+        return Self::new(Method::GET, Base::Reports, path);
+    }
+
+    fn v9_get(path: String) -> Self {
+        return Self::new(Method::GET, Base::TrackV9, path);
+    }
+
+    fn v9_post(path: String) -> Self {
+        return Self::new(Method::POST, Base::TrackV9, path);
+    }
+
+    /// Pass the fields of `params` as query params.
+    fn query<Params: Serialize>(mut self, params: &Params) -> EndpointResult<BlobJson, ErrorJson> {
+        self.query = query::to_query_pairs(params).map_err(ApiError::Query)?;
+        return Ok(self);
+    }
+
+    /// Send `body` as JSON.
+    fn body<Body: Serialize>(mut self, body: &Body) -> EndpointResult<BlobJson, ErrorJson> {
+        let json = serde_json::to_value(body)
+            .map_err(|err| ApiError::Query(QueryError::BodySerialization(err)))?;
+        self.body = Some(json);
+        return Ok(self);
+    }
+
+    /// The full URL of the endpoint, given the URLs of the APIs.
+    pub(crate) fn url(&self, base_urls: &BaseUrls) -> ApiResult<Url, ErrorJson> {
+        let base_url = base_urls.of(self.base);
+        return Url::parse_with_params(&(base_url.to_owned() + &self.path), &self.query)
+            .map_err(|err| ApiError::Query(QueryError::Url(err)));
+    }
+}
+
+fn ids_path(ids: &[TimeEntryId]) -> String {
+    return ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+}
+
+/// Open a session with `version` of the API, whose cookie is in the response. Shared by every
+/// client, hence `ErrorJson`.
+pub(crate) fn session_create<ErrorJson: DeserializeOwned>(
+    version: ApiVersion,
+) -> EndpointResult<IgnoredAny, ErrorJson> {
+    return Ok(match version {
+        ApiVersion::V8 => Endpoint::post("/sessions".to_string()),
+        ApiVersion::V9 => Endpoint::v9_post("/me/sessions".to_string()),
+    });
+}
+
+pub(crate) fn time_entry_create(
+    time_entry: &TimeEntry,
+) -> EndpointResult<TimeEntryResponse, DefaultErrorJson> {
+    return Endpoint::post("/time_entries".to_string()).body(&TimeEntryRequest {
+        time_entry: time_entry.clone(),
+    });
+}
+
+pub(crate) fn time_entry_get(
+    id: TimeEntryId,
+) -> EndpointResult<TimeEntryResponse, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/time_entries/{}", id)));
+}
+
+pub(crate) fn time_entry_update(
+    id: TimeEntryId,
+    time_entry: &TimeEntry,
+) -> EndpointResult<TimeEntryResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/time_entries/{}", id)).body(&TimeEntryRequest {
+        time_entry: time_entry.clone(),
+    });
+}
+
+pub(crate) fn time_entry_delete(id: TimeEntryId) -> EndpointResult<IgnoredAny, DefaultErrorJson> {
+    return Ok(Endpoint::delete(format!("/time_entries/{}", id)));
+}
+
+pub(crate) fn time_entries(
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> EndpointResult<Vec<TimeEntry>, DefaultErrorJson> {
+    return Endpoint::get("/time_entries".to_string()).query(&TimeEntriesParams {
+        start_date,
+        end_date,
+    });
+}
+
+/// Update the time entries with the given `ids`, which should be at most `BULK_CHUNK_SIZE`.
+pub(crate) fn time_entries_bulk_update(
+    ids: &[TimeEntryId],
+    patch: &TimeEntryPatch,
+) -> EndpointResult<TimeEntriesResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/time_entries/{}", ids_path(ids)))
+        .body(&TimeEntryPatchRequest { time_entry: patch });
+}
+
+/// Delete the time entries with the given `ids`, which should be at most `BULK_CHUNK_SIZE`.
+pub(crate) fn time_entries_bulk_delete(
+    ids: &[TimeEntryId],
+) -> EndpointResult<IgnoredAny, DefaultErrorJson> {
+    return Ok(Endpoint::delete(format!("/time_entries/{}", ids_path(ids))));
+}
+
+pub(crate) fn time_entry_start(
+    time_entry: &TimeEntryStart,
+) -> EndpointResult<TimeEntryResponse, DefaultErrorJson> {
+    return Endpoint::post("/time_entries/start".to_string()).body(&TimeEntryStartRequest {
+        time_entry: time_entry.clone(),
+    });
+}
+
+pub(crate) fn time_entry_stop(
+    id: TimeEntryId,
+) -> EndpointResult<TimeEntryResponse, DefaultErrorJson> {
+    return Ok(Endpoint::put(format!("/time_entries/{}/stop", id)));
+}
+
+pub(crate) fn time_entry_current() -> EndpointResult<CurrentTimeEntryResponse, DefaultErrorJson> {
+    return Ok(Endpoint::get("/time_entries/current".to_string()));
+}
+
+pub(crate) fn workspaces_get_all() -> EndpointResult<Vec<Workspace>, DefaultErrorJson> {
+    return Ok(Endpoint::get("/workspaces".to_string()));
+}
+
+pub(crate) fn workspaces_tags_all(wid: WorkspaceId) -> EndpointResult<Vec<Tag>, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/workspaces/{}/tags", wid)));
+}
+
+pub(crate) fn workspaces_projects_all(
+    wid: WorkspaceId,
+) -> EndpointResult<Vec<Project>, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/workspaces/{}/projects", wid)));
+}
+
+pub(crate) fn project_create(
+    project: &Project,
+) -> EndpointResult<ProjectResponse, DefaultErrorJson> {
+    return Endpoint::post("/projects".to_string()).body(&ProjectRequest { project });
+}
+
+pub(crate) fn project_get(id: ProjectId) -> EndpointResult<ProjectResponse, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/projects/{}", id)));
+}
+
+pub(crate) fn project_update(
+    id: ProjectId,
+    project: &Project,
+) -> EndpointResult<ProjectResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/projects/{}", id)).body(&ProjectRequest { project });
+}
+
+pub(crate) fn project_delete(id: ProjectId) -> EndpointResult<IgnoredAny, DefaultErrorJson> {
+    return Ok(Endpoint::delete(format!("/projects/{}", id)));
+}
+
+pub(crate) fn project_archive(id: ProjectId) -> EndpointResult<ProjectResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/projects/{}", id)).body(&ProjectActiveRequest {
+        project: ProjectActive { active: false },
+    });
+}
+
+pub(crate) fn tag_create(tag: &Tag) -> EndpointResult<TagResponse, DefaultErrorJson> {
+    return Endpoint::post("/tags".to_string()).body(&TagRequest { tag });
+}
+
+pub(crate) fn tag_update(id: TagId, tag: &Tag) -> EndpointResult<TagResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/tags/{}", id)).body(&TagRequest { tag });
+}
+
+pub(crate) fn tag_delete(id: TagId) -> EndpointResult<IgnoredAny, DefaultErrorJson> {
+    return Ok(Endpoint::delete(format!("/tags/{}", id)));
+}
+
+pub(crate) fn task_create(task: &Task) -> EndpointResult<TaskResponse, DefaultErrorJson> {
+    return Endpoint::post("/tasks".to_string()).body(&TaskRequest { task });
+}
+
+pub(crate) fn task_get(id: TaskId) -> EndpointResult<TaskResponse, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/tasks/{}", id)));
+}
+
+pub(crate) fn task_update(
+    id: TaskId,
+    task: &Task,
+) -> EndpointResult<TaskResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/tasks/{}", id)).body(&TaskRequest { task });
+}
+
+pub(crate) fn task_delete(id: TaskId) -> EndpointResult<IgnoredAny, DefaultErrorJson> {
+    return Ok(Endpoint::delete(format!("/tasks/{}", id)));
+}
+
+pub(crate) fn projects_tasks_all(
+    pid: ProjectId,
+    active: TasksActive,
+) -> EndpointResult<Option<Vec<Task>>, DefaultErrorJson> {
+    return Endpoint::get(format!("/projects/{}/tasks", pid)).query(&TasksParams { active });
+}
+
+pub(crate) fn workspaces_tasks_all(
+    wid: WorkspaceId,
+    active: TasksActive,
+) -> EndpointResult<Option<Vec<Task>>, DefaultErrorJson> {
+    return Endpoint::get(format!("/workspaces/{}/tasks", wid)).query(&TasksParams { active });
+}
+
+pub(crate) fn workspaces_users_all(
+    wid: WorkspaceId,
+) -> EndpointResult<Vec<WorkspaceUser>, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/workspaces/{}/users", wid)));
+}
+
+pub(crate) fn workspaces_groups_all(
+    wid: WorkspaceId,
+) -> EndpointResult<Option<Vec<Group>>, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/workspaces/{}/groups", wid)));
+}
+
+pub(crate) fn projects_users_all(
+    pid: ProjectId,
+) -> EndpointResult<Option<Vec<ProjectUser>>, DefaultErrorJson> {
+    return Endpoint::get(format!("/projects/{}/project_users", pid))
+        .query(&ProjectUsersParams { fields: "fullname" });
+}
+
+pub(crate) fn dashboard(wid: WorkspaceId) -> EndpointResult<Dashboard, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/dashboard/{}", wid)));
+}
+
+pub(crate) fn workspaces_clients_all(
+    wid: WorkspaceId,
+) -> EndpointResult<Option<Vec<Client>>, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/workspaces/{}/clients", wid)));
+}
+
+pub(crate) fn client_create(client: &Client) -> EndpointResult<ClientResponse, DefaultErrorJson> {
+    return Endpoint::post("/clients".to_string()).body(&ClientRequest { client });
+}
+
+pub(crate) fn client_get(id: ClientId) -> EndpointResult<ClientResponse, DefaultErrorJson> {
+    return Ok(Endpoint::get(format!("/clients/{}", id)));
+}
+
+pub(crate) fn client_update(
+    id: ClientId,
+    client: &Client,
+) -> EndpointResult<ClientResponse, DefaultErrorJson> {
+    return Endpoint::put(format!("/clients/{}", id)).body(&ClientRequest { client });
+}
+
+pub(crate) fn client_delete(id: ClientId) -> EndpointResult<IgnoredAny, DefaultErrorJson> {
+    return Ok(Endpoint::delete(format!("/clients/{}", id)));
+}
+
+pub(crate) fn client_projects(
+    id: ClientId,
+    active: ProjectsActive,
+) -> EndpointResult<Option<Vec<Project>>, DefaultErrorJson> {
+    return Endpoint::get(format!("/clients/{}/projects", id))
+        .query(&ClientProjectsParams { active });
+}
+
+pub(crate) fn reports_detailed(
+    params: &ReportsDetailedParams,
+) -> EndpointResult<Report<ReportTimeEntry>, ReportsErrorJson> {
+    return Endpoint::reports("/details".to_string()).query(params);
+}
+
+pub(crate) fn reports_summary(
+    params: &ReportsSummaryParams,
+) -> EndpointResult<SummaryReport, ReportsErrorJson> {
+    return Endpoint::reports("/summary".to_string()).query(params);
+}
+
+pub(crate) fn reports_weekly(
+    params: &ReportsWeeklyParams,
+) -> EndpointResult<WeeklyReport, ReportsErrorJson> {
+    return Endpoint::reports("/weekly".to_string()).query(params);
+}
+
+/// The response is a file rather than JSON, hence no `BlobJson`.
+pub(crate) fn reports_export<Params: ReportsExportParams>(
+    params: &Params,
+    format: ExportFormat,
+) -> EndpointResult<(), ReportsErrorJson> {
+    return Endpoint::reports(params.export_path(format)).query(params);
+}
+
+pub(crate) fn current_user(
+    since: Option<DateTime<Utc>>,
+) -> EndpointResult<UserResponse, DefaultErrorJson> {
+    let endpoint = Endpoint::get("/me".to_string());
+    return match since {
+        // Add params if since is passed
+        Some(datetime) => endpoint.query(&CurrentUserParams {
+            with_related_data: true,
+            since: datetime.timestamp(),
+        }),
+        None => Ok(endpoint),
+    };
+}
+
+pub(crate) fn current_user_update(
+    user: &UserUpdate,
+) -> EndpointResult<UserResponse, DefaultErrorJson> {
+    return Endpoint::put("/me".to_string()).body(&UserUpdateRequest { user });
+}
+
+pub(crate) fn reset_api_token() -> EndpointResult<String, DefaultErrorJson> {
+    return Ok(Endpoint::post("/reset_token".to_string()));
+}
+
+/// The endpoints of `crate::v9::Api`.
+pub(crate) mod v9 {
+    use super::*;
+    use crate::v9::{ErrorJson, Project, Tag, TimeEntry, User, Workspace};
+
+    /// The query params of `current_user`
+    #[derive(Serialize, Debug)]
+    struct CurrentUserParams {
+        with_related_data: bool,
+    }
+
+    /// v9 takes the time entry as is, without a `{"time_entry": ...}` wrapper.
+    pub(crate) fn time_entry_create(
+        time_entry: &TimeEntry,
+    ) -> EndpointResult<TimeEntry, ErrorJson> {
+        return Endpoint::v9_post(format!(
+            "/workspaces/{}/time_entries",
+            time_entry.workspace_id
+        ))
+        .body(time_entry);
+    }
+
+    pub(crate) fn workspaces_get_all() -> EndpointResult<Vec<Workspace>, ErrorJson> {
+        return Ok(Endpoint::v9_get("/me/workspaces".to_string()));
+    }
+
+    pub(crate) fn workspaces_tags_all(
+        wid: WorkspaceId,
+    ) -> EndpointResult<Option<Vec<Tag>>, ErrorJson> {
+        return Ok(Endpoint::v9_get(format!("/workspaces/{}/tags", wid)));
+    }
+
+    pub(crate) fn workspaces_projects_all(
+        wid: WorkspaceId,
+    ) -> EndpointResult<Option<Vec<Project>>, ErrorJson> {
+        return Ok(Endpoint::v9_get(format!("/workspaces/{}/projects", wid)));
+    }
+
+    pub(crate) fn current_user(with_related_data: bool) -> EndpointResult<User, ErrorJson> {
+        return Endpoint::v9_get("/me".to_string()).query(&CurrentUserParams { with_related_data });
+    }
+}
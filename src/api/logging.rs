@@ -0,0 +1,81 @@
+//! Request tracing, through `log`. Nothing is output unless the application installs a logger
+//! (`env_logger`, ...), and secrets are redacted from the URLs that are logged.
+use std::time::Duration;
+
+use log::{debug, warn};
+use reqwest::{Method, StatusCode, Url};
+
+/// The query params whose values are never logged.
+const SECRET_PARAMS: [&str; 4] = ["api_token", "token", "password", "api_key"];
+
+/// `url` without credentials, to be logged.
+pub(crate) fn redacted(url: &Url) -> String {
+    let mut url = url.clone();
+    if !url.username().is_empty() || url.password().is_some() {
+        // Only fails for URLs that can't have credentials anyway.
+        let _ = url.set_username("REDACTED");
+        let _ = url.set_password(None);
+    }
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if SECRET_PARAMS.contains(&key.as_ref()) {
+                    "REDACTED".to_owned()
+                } else {
+                    value.into_owned()
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    return url.to_string();
+}
+
+/// Log an attempt at a request that got a response.
+pub(crate) fn response(
+    method: &Method,
+    url: &str,
+    status_code: StatusCode,
+    latency: Duration,
+    retries: u32,
+) {
+    debug!(
+        "{} {} -> {} in {}ms (retries: {})",
+        method,
+        url,
+        status_code,
+        latency.as_millis(),
+        retries
+    );
+}
+
+/// Log an attempt at a request that didn't get a response.
+pub(crate) fn network_error(
+    method: &Method,
+    url: &str,
+    err: &reqwest::Error,
+    latency: Duration,
+    retries: u32,
+) {
+    debug!(
+        "{} {} -> failed in {}ms (retries: {}): {}",
+        method,
+        url,
+        latency.as_millis(),
+        retries,
+        err
+    );
+}
+
+/// Log that a request is going to be retried.
+pub(crate) fn retry(method: &Method, url: &str, delay: Duration, retries: u32) {
+    warn!(
+        "Retrying {} {} in {}ms (retry {})",
+        method,
+        url,
+        delay.as_millis(),
+        retries + 1
+    );
+}
@@ -0,0 +1,160 @@
+//! Rate limiting and retries, shared by the blocking and async clients. Toggl asks clients to
+//! stay at about one request per second, and answers 429 when they don't.
+// https://github.com/toggl/toggl_api_docs
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+
+/// How failed requests are retried. Only requests with idempotent methods (GET, PUT, DELETE, ...)
+/// are retried, when they fail because of the network, because Toggl is rate limiting us (429),
+/// or because Toggl is unavailable (502, 503, 504).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// How many times a request is retried before giving up. 0 disables retries.
+    pub max_retries: u32,
+
+    /// How long to wait before the first retry. It doubles with every retry, with some jitter.
+    pub initial_backoff: Duration,
+
+    /// The longest to wait between two attempts, unless Toggl asks for more with Retry-After.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Why an attempt at a request failed.
+pub(crate) enum Failure<'r> {
+    Network(&'r reqwest::Error),
+    Status {
+        status_code: StatusCode,
+        headers: &'r HeaderMap,
+    },
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// How long to wait before retrying a request that failed with `failure`, after `retries`
+    /// retries. `None` if it shouldn't be retried.
+    pub(crate) fn retry_delay(
+        &self,
+        method: &Method,
+        retries: u32,
+        failure: Failure,
+    ) -> Option<Duration> {
+        if retries >= self.max_retries || !method.is_idempotent() {
+            return None;
+        }
+        let retry_after = match failure {
+            Failure::Network(err) => {
+                if !(err.is_connect() || err.is_timeout() || err.is_request()) {
+                    return None;
+                }
+                None
+            }
+            Failure::Status {
+                status_code,
+                headers,
+            } => {
+                let retryable = matches!(
+                    status_code,
+                    StatusCode::TOO_MANY_REQUESTS
+                        | StatusCode::BAD_GATEWAY
+                        | StatusCode::SERVICE_UNAVAILABLE
+                        | StatusCode::GATEWAY_TIMEOUT
+                );
+                if !retryable {
+                    return None;
+                }
+                retry_after(headers)
+            }
+        };
+        return Some(retry_after.unwrap_or_else(|| self.backoff(retries)));
+    }
+
+    /// Exponential backoff with jitter: somewhere between half and all of
+    /// `initial_backoff * 2^retries`, capped at `max_backoff`.
+    fn backoff(&self, retries: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retries);
+        let backoff = self
+            .initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        return backoff / 2 + backoff.mul_f64(random_fraction() / 2.0);
+    }
+}
+
+/// How long Toggl asks us to wait, if it does. Retry-After is either a number of seconds, or a
+/// date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    return Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    );
+}
+
+/// A random number in [0, 1), good enough for jitter. Saves a dependency on `rand`: the keys of
+/// `RandomState` are random, and the current time varies them between calls.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO);
+    hasher.write_u128(now.as_nanos());
+    return (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+}
+
+/// Spaces requests out by at least `interval`. Requests reserve their slot before being sent,
+/// so concurrent requests are spaced out too.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// Reserve the next slot, and return how long to wait until it.
+    pub(crate) fn reserve(&self) -> Duration {
+        let now = Instant::now();
+        let mut next_slot = self.next_slot.lock().unwrap();
+        let slot = match *next_slot {
+            Some(slot) if slot > now => slot,
+            _ => now,
+        };
+        *next_slot = Some(slot + self.interval);
+        return slot - now;
+    }
+}
@@ -0,0 +1,55 @@
+//! Sessions of users who log in with their email and password. The password is only sent to open
+//! the session, and the following requests carry its cookie instead. Shared by the blocking and
+//! async clients.
+// https://github.com/toggl/toggl_api_docs/blob/master/chapters/authentication.md#session-cookie
+use std::sync::Mutex;
+
+use reqwest::header::{HeaderMap, SET_COOKIE};
+use reqwest::StatusCode;
+
+/// The cookies Toggl keeps sessions in: the one of v8, and the one of v9.
+const SESSION_COOKIES: [&str; 2] = ["toggl_api_session_new", "__Host-timer-session"];
+
+/// The cookie of the current session, if one was opened. Clones of a client share it.
+#[derive(Debug, Default)]
+pub(crate) struct Session {
+    cookie: Mutex<Option<String>>,
+}
+
+impl Session {
+    /// The `Cookie` header of the session, e.g. "toggl_api_session_new=abc".
+    pub(crate) fn cookie(&self) -> Option<String> {
+        return self.cookie.lock().unwrap().clone();
+    }
+
+    /// Keep the session cookie set by the response to opening a session. Returns it, or `None`
+    /// if the response doesn't set one.
+    pub(crate) fn open(&self, headers: &HeaderMap) -> Option<String> {
+        let cookie = headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| value.split(';').next())
+            .find(|pair| {
+                SESSION_COOKIES
+                    .iter()
+                    .any(|name| pair.trim_start().starts_with(&format!("{}=", name)))
+            })
+            .map(|pair| pair.trim().to_owned());
+        *self.cookie.lock().unwrap() = cookie.clone();
+        return cookie;
+    }
+
+    /// Forget the session if Toggl refused it (it expired, or was closed elsewhere), so that the
+    /// next request opens another one.
+    pub(crate) fn check(&self, status_code: StatusCode) {
+        if is_refused(status_code) {
+            *self.cookie.lock().unwrap() = None;
+        }
+    }
+}
+
+/// Whether a response with `status_code` means that the session was refused.
+pub(crate) fn is_refused(status_code: StatusCode) -> bool {
+    return status_code == StatusCode::UNAUTHORIZED || status_code == StatusCode::FORBIDDEN;
+}
@@ -0,0 +1,119 @@
+//! The ids of Toggl's objects, one type per kind of object so that a project id can't be passed
+//! where a workspace id is expected. They (de)serialize as plain numbers, and are stored in the
+//! `BigInt` columns of `schema.rs`, since Toggl's ids don't all fit in 32 bits. The tables keep
+//! their `INTEGER` columns: SQLite's integers are 64-bit already, and an `INTEGER PRIMARY KEY` is
+//! what makes SQLite assign the ids of rows inserted without one. `diesel print-schema` maps
+//! them to `Integer` though, so `src/schema.patch` turns them back into `BigInt`s.
+// diesel 1.4's derives wrap their impls in functions.
+#![allow(non_local_definitions)]
+use std::fmt;
+use std::io::Write;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::BigInt;
+use diesel::sqlite::Sqlite;
+use diesel::{AsExpression, FromSqlRow};
+use serde::{Deserialize, Serialize};
+
+macro_rules! id_type {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(
+            Serialize,
+            Deserialize,
+            Debug,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            Hash,
+            PartialOrd,
+            Ord,
+            Default,
+            AsExpression,
+            FromSqlRow,
+        )]
+        #[serde(transparent)]
+        #[sql_type = "BigInt"]
+        pub struct $name(pub i64);
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                return self.0.fmt(f);
+            }
+        }
+
+        impl From<i64> for $name {
+            fn from(id: i64) -> Self {
+                return $name(id);
+            }
+        }
+
+        impl From<$name> for i64 {
+            fn from(id: $name) -> Self {
+                return id.0;
+            }
+        }
+
+        impl ToSql<BigInt, Sqlite> for $name {
+            fn to_sql<W: Write>(&self, out: &mut Output<W, Sqlite>) -> serialize::Result {
+                return <i64 as ToSql<BigInt, Sqlite>>::to_sql(&self.0, out);
+            }
+        }
+
+        impl FromSql<BigInt, Sqlite> for $name {
+            fn from_sql(value: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
+                let id = <i64 as FromSql<BigInt, Sqlite>>::from_sql(value)?;
+                return Ok($name(id));
+            }
+        }
+    };
+}
+
+id_type!(
+    /// The id of a workspace: `wid` in most of Toggl's objects.
+    WorkspaceId
+);
+id_type!(
+    /// The id of a project: `pid` in most of Toggl's objects.
+    ProjectId
+);
+id_type!(
+    /// The id of a task: `tid` in most of Toggl's objects.
+    TaskId
+);
+id_type!(
+    /// The id of a client: `cid` in most of Toggl's objects.
+    ClientId
+);
+id_type!(
+    /// The id of a user: `uid` in most of Toggl's objects.
+    UserId
+);
+id_type!(
+    /// The id of a tag.
+    TagId
+);
+id_type!(
+    /// The id of a time entry.
+    TimeEntryId
+);
+id_type!(
+    /// The id of the membership of a user in a project.
+    ProjectUserId
+);
+id_type!(
+    /// The id of a group of users.
+    GroupId
+);
+id_type!(
+    /// The id of an organization, which workspaces belong to.
+    OrganizationId
+);
+id_type!(
+    /// The id of a group of a summary report: a project, client or user id, depending on the
+    /// grouping of the report.
+    SummaryGroupId
+);
@@ -0,0 +1,27 @@
+use std::str::FromStr;
+
+pub mod api;
+pub mod ids;
+pub mod query;
+pub mod v9;
+
+/// The versions of the Toggl Track API. `api::Api` talks to v8, and `v9::Api` to v9: build one
+/// with `api::ApiBuilder::build` or `api::ApiBuilder::build_v9`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    V8,
+    V9,
+}
+
+impl FromStr for ApiVersion {
+    type Err = String;
+
+    /// Parses "v8" or "v9"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s {
+            "v8" => Ok(ApiVersion::V8),
+            "v9" => Ok(ApiVersion::V9),
+            other => Err(format!("Unknown API version: {}", other)),
+        };
+    }
+}
@@ -1,19 +1,45 @@
-mod api;
-
 use std::env;
+use std::process;
 use chrono::{Duration, Utc};
+use toggl_oxide::{api, v9, ApiVersion};
 
 fn main() {
-    let api_key = env::var("TOGGL_API_KEY").expect("Need to set TOGGL_API_KEY env var");
-    let api_client = api::Api::new(&api_key);
+    let credentials = api::Credentials::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    let version = match env::var("TOGGL_API_VERSION") {
+        Ok(version) => version.parse().unwrap_or_else(|err| {
+            eprintln!("Invalid TOGGL_API_VERSION: {}", err);
+            process::exit(1);
+        }),
+        Err(_) => ApiVersion::V8,
+    };
+
+    let builder = api::ApiBuilder::new(credentials);
+    match version {
+        ApiVersion::V8 => run_v8(builder.build().expect("Couldn't build the API client")),
+        ApiVersion::V9 => run_v9(builder.build_v9().expect("Couldn't build the API client")),
+    }
+}
+
+fn run_v8(api_client: api::Api) {
     let workspaces = api_client.workspaces_get_all().unwrap();
-    println!("{:?}", api_client.workspaces_get_all().unwrap());
+    println!("{:?}", workspaces);
     println!("{:?}", api_client.current_user(None));
     let since = Utc::now() - Duration::weeks(2);
     println!("{:?}", api_client.current_user(Some(since)).unwrap());
     println!("{:?}", api_client.workspaces_projects_all(workspaces[0].id.unwrap()));
     println!("{:?}", api_client.workspaces_tags_all(workspaces[0].id.unwrap()));
 
-    let params = api::ReportsDetailedParams::new("Toggle Oxide".to_string(), 5864726, 1);
+    let params = api::ReportsDetailedParams::new("Toggle Oxide".to_string(), api::WorkspaceId(5864726), 1);
     println!("{:?}", api_client.reports_detailed(&params));
 }
+
+fn run_v9(api_client: v9::Api) {
+    let workspaces = api_client.workspaces_get_all().unwrap();
+    println!("{:?}", workspaces);
+    println!("{:?}", api_client.current_user(true));
+    println!("{:?}", api_client.workspaces_projects_all(workspaces[0].id));
+    println!("{:?}", api_client.workspaces_tags_all(workspaces[0].id));
+}
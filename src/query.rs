@@ -0,0 +1,259 @@
+//! Serialize params structs into query strings, the way Toggl expects them.
+//!
+//! Fields are serialized with serde, then flattened into `key=value` pairs:
+//! - `None`s are skipped
+//! - booleans become "true"/"false" (use `on_off` for the fields that expect "on"/"off")
+//! - arrays of numbers, strings or booleans are joined with commas
+//! - dates are whatever the field serializes to (use `date` for YYYY-MM-DD)
+use std::error;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+/// Errors that can happen when building a query string out of a params struct, or the body of a
+/// request.
+#[derive(Debug)]
+pub enum QueryError {
+    /// Serde couldn't serialize the params
+    Serialization(serde_json::Error),
+
+    /// Serde couldn't serialize the body of the request
+    BodySerialization(serde_json::Error),
+
+    /// The params didn't serialize to a map of keys to values
+    NotAMap(Value),
+
+    /// The value under `key` can't be represented in a query string (nested maps or arrays)
+    UnsupportedValue { key: String, value: Value },
+
+    /// The base URL is invalid
+    Url(url::ParseError),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            QueryError::Serialization(err) => write!(f, "Couldn't serialize the params: {}", err),
+            QueryError::BodySerialization(err) => {
+                write!(f, "Couldn't serialize the body: {}", err)
+            }
+            QueryError::NotAMap(value) => {
+                write!(f, "The params serialized to {} instead of a map", value)
+            }
+            QueryError::UnsupportedValue { key, value } => write!(
+                f,
+                "The value of {} can't be put in a query string: {}",
+                key, value
+            ),
+            QueryError::Url(err) => write!(f, "Invalid URL: {}", err),
+        };
+    }
+}
+
+impl error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return match self {
+            QueryError::Serialization(err) | QueryError::BodySerialization(err) => Some(err),
+            QueryError::Url(err) => Some(err),
+            QueryError::NotAMap(_) | QueryError::UnsupportedValue { .. } => None,
+        };
+    }
+}
+
+/// Turn a single scalar into its query string representation.
+fn scalar_to_string(value: Value) -> Result<String, Value> {
+    return match value {
+        Value::Bool(val) => Ok(val.to_string()),
+        Value::Number(val) => Ok(val.to_string()),
+        Value::String(val) => Ok(val),
+        other => Err(other),
+    };
+}
+
+/// Serialize `params` into a list of query params.
+pub fn to_query_pairs<Params: Serialize>(
+    params: &Params,
+) -> Result<Vec<(String, String)>, QueryError> {
+    let map = match serde_json::to_value(params).map_err(QueryError::Serialization)? {
+        Value::Object(map) => map,
+        other => return Err(QueryError::NotAMap(other)),
+    };
+
+    let mut query_params = vec![];
+    for (key, wrapped_val) in map.into_iter() {
+        let to_append = match wrapped_val {
+            Value::Null => continue,
+            Value::Array(vals) => {
+                let mut items = vec![];
+                for val in vals {
+                    match scalar_to_string(val) {
+                        Ok(item) => items.push(item),
+                        Err(value) => return Err(QueryError::UnsupportedValue { key, value }),
+                    }
+                }
+                items.join(",")
+            }
+            val => match scalar_to_string(val) {
+                Ok(item) => item,
+                Err(value) => return Err(QueryError::UnsupportedValue { key, value }),
+            },
+        };
+        query_params.push((key, to_append));
+    }
+    return Ok(query_params);
+}
+
+/// Serialize an optional boolean as "on" or "off". Use with
+/// `#[serde(serialize_with = "crate::query::on_off")]`.
+pub fn on_off<S: Serializer>(val: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error> {
+    return match val {
+        Some(true) => serializer.serialize_str("on"),
+        Some(false) => serializer.serialize_str("off"),
+        None => serializer.serialize_none(),
+    };
+}
+
+/// Serialize an optional datetime as an ISO 8601 date (YYYY-MM-DD). Use with
+/// `#[serde(serialize_with = "crate::query::date")]`.
+pub fn date<S: Serializer>(val: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error> {
+    return match val {
+        Some(datetime) => serializer.serialize_str(&datetime.format("%Y-%m-%d").to_string()),
+        None => serializer.serialize_none(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use reqwest::Url;
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::*;
+
+    /// Build a URL out of `base` with the fields of `params` as query params.
+    fn url_with_params<Params: Serialize>(base: &str, params: &Params) -> Result<Url, QueryError> {
+        let query_params = to_query_pairs(params)?;
+        return Url::parse_with_params(base, query_params).map_err(QueryError::Url);
+    }
+
+    #[derive(Serialize)]
+    struct Params {
+        user_agent: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        project_ids: Option<Vec<i64>>,
+        #[serde(serialize_with = "on_off")]
+        distinct_rates: Option<bool>,
+        #[serde(serialize_with = "date")]
+        since: Option<DateTime<Utc>>,
+        without_description: Option<bool>,
+    }
+
+    fn params() -> Params {
+        return Params {
+            user_agent: "toggl oxide",
+            project_ids: Some(vec![1, 22, 333]),
+            distinct_rates: Some(true),
+            since: Some(Utc.ymd(2021, 12, 1).and_hms(9, 30, 0)),
+            without_description: Some(false),
+        };
+    }
+
+    #[test]
+    fn params_are_flattened_into_pairs() {
+        let pairs = to_query_pairs(&params()).unwrap();
+        // serde_json sorts the keys.
+        let expected = [
+            ("distinct_rates", "on"),
+            ("project_ids", "1,22,333"),
+            ("since", "2021-12-01"),
+            ("user_agent", "toggl oxide"),
+            ("without_description", "false"),
+        ];
+        let expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn nones_are_skipped() {
+        let params = Params {
+            project_ids: None,
+            distinct_rates: None,
+            since: None,
+            without_description: None,
+            ..params()
+        };
+        let url = url_with_params("https://toggl.test/details", &params).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://toggl.test/details?user_agent=toggl+oxide"
+        );
+    }
+
+    #[test]
+    fn off_and_arrays_of_strings_and_booleans_are_joined() {
+        #[derive(Serialize)]
+        struct Params {
+            #[serde(serialize_with = "on_off")]
+            rounding: Option<bool>,
+            names: Vec<&'static str>,
+            flags: Vec<bool>,
+        }
+
+        let params = Params {
+            rounding: Some(false),
+            names: vec!["a", "b"],
+            flags: vec![true, false],
+        };
+        let url = url_with_params("https://toggl.test/", &params).unwrap();
+        assert_eq!(
+            url.query(),
+            Some("flags=true%2Cfalse&names=a%2Cb&rounding=off")
+        );
+    }
+
+    #[test]
+    fn nested_values_are_unsupported() {
+        #[derive(Serialize)]
+        struct Params {
+            nested: Vec<Vec<i64>>,
+        }
+
+        let result = to_query_pairs(&Params {
+            nested: vec![vec![1]],
+        });
+        match result {
+            Err(QueryError::UnsupportedValue { key, value }) => {
+                assert_eq!(key, "nested");
+                assert_eq!(value, json!([1]));
+            }
+            other => panic!("Expected an unsupported value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn params_must_be_a_map() {
+        let result = to_query_pairs(&vec![1, 2]);
+        assert!(
+            matches!(result, Err(QueryError::NotAMap(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn body_errors_are_not_reported_as_params_errors() {
+        let err = serde_json::from_str::<i64>("x").unwrap_err();
+        let message = QueryError::BodySerialization(err).to_string();
+        assert!(
+            message.starts_with("Couldn't serialize the body"),
+            "{}",
+            message
+        );
+    }
+}
@@ -1,23 +1,23 @@
 table! {
     clients (id) {
-        id -> Nullable<Integer>,
-        wid -> Integer,
+        id -> Nullable<BigInt>,
+        wid -> BigInt,
         name -> Text,
         at -> Text,
-        user_id -> Integer,
+        user_id -> BigInt,
     }
 }
 
 table! {
     projects (id) {
-        id -> Nullable<Integer>,
+        id -> Nullable<BigInt>,
         name -> Text,
-        wid -> Integer,
-        cid -> Nullable<Integer>,
+        wid -> BigInt,
+        cid -> Nullable<BigInt>,
         active -> Bool,
         is_private -> Bool,
         template -> Nullable<Bool>,
-        template_id -> Nullable<Integer>,
+        template_id -> Nullable<BigInt>,
         billable -> Nullable<Bool>,
         auto_estimates -> Nullable<Bool>,
         estimated_hours -> Nullable<Integer>,
@@ -30,26 +30,40 @@ table! {
 
 table! {
     tags (id) {
-        id -> Nullable<Integer>,
+        id -> Nullable<BigInt>,
         name -> Text,
-        wid -> Integer,
-        user_id -> Integer,
+        wid -> BigInt,
+        user_id -> BigInt,
+    }
+}
+
+table! {
+    tasks (id) {
+        id -> Nullable<BigInt>,
+        name -> Text,
+        pid -> BigInt,
+        wid -> BigInt,
+        uid -> Nullable<BigInt>,
+        estimated_seconds -> Nullable<Integer>,
+        active -> Bool,
+        at -> Text,
+        tracked_seconds -> Nullable<Integer>,
     }
 }
 
 table! {
     time_entry_tag_join (time_entry_id, tag_id) {
-        time_entry_id -> Integer,
-        tag_id -> Integer,
+        time_entry_id -> BigInt,
+        tag_id -> BigInt,
     }
 }
 
 table! {
     time_entrys (id) {
-        id -> Nullable<Integer>,
+        id -> Nullable<BigInt>,
         description -> Text,
-        wid -> Nullable<Integer>,
-        pid -> Nullable<Integer>,
+        wid -> Nullable<BigInt>,
+        pid -> Nullable<BigInt>,
         billable -> Nullable<Bool>,
         start -> Text,
         stop -> Nullable<Text>,
@@ -57,14 +71,15 @@ table! {
         created_with -> Nullable<Text>,
         duronly -> Nullable<Bool>,
         at -> Nullable<Text>,
+        tid -> Nullable<BigInt>,
     }
 }
 
 table! {
     users (id) {
-        id -> Nullable<Integer>,
+        id -> Nullable<BigInt>,
         api_token -> Integer,
-        default_wid_id -> Integer,
+        default_wid_id -> BigInt,
         email -> Text,
         fullname -> Text,
         jquery_timeofday_format -> Text,
@@ -87,7 +102,7 @@ table! {
 
 table! {
     workspaces (id) {
-        id -> Nullable<Integer>,
+        id -> Nullable<BigInt>,
         name -> Text,
         premium -> Bool,
         admin -> Bool,
@@ -99,15 +114,18 @@ table! {
         rounding_minutes -> Integer,
         at -> Text,
         logo_url -> Nullable<Text>,
-        user_id -> Integer,
+        user_id -> BigInt,
     }
 }
 
 joinable!(clients -> users (user_id));
 joinable!(tags -> users (user_id));
+joinable!(tasks -> projects (pid));
+joinable!(tasks -> workspaces (wid));
 joinable!(time_entry_tag_join -> tags (tag_id));
 joinable!(time_entry_tag_join -> time_entrys (time_entry_id));
 joinable!(time_entrys -> projects (pid));
+joinable!(time_entrys -> tasks (tid));
 joinable!(time_entrys -> workspaces (wid));
 joinable!(workspaces -> users (user_id));
 
@@ -115,6 +133,7 @@ allow_tables_to_appear_in_same_query!(
     clients,
     projects,
     tags,
+    tasks,
     time_entry_tag_join,
     time_entrys,
     users,
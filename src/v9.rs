@@ -0,0 +1,251 @@
+//! Client for version 9 of the Toggl Track API, which replaces v8 (see `crate::api`).
+//!
+//! v9 drops the `{"data": ...}` and `{"time_entry": ...}` wrappers, scopes time entries to a
+//! workspace, and spells out ids (`workspace_id` instead of `wid`, ...). Reports are unchanged,
+//! so they are still fetched through `crate::api::Api`.
+// https://developers.track.toggl.com/docs/
+use std::fmt;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::endpoints::v9 as endpoints;
+use crate::api::{ApiBuilder, ApiResult, Credentials};
+use crate::ids::{
+    ClientId, OrganizationId, ProjectId, TagId, TaskId, TimeEntryId, UserId, WorkspaceId,
+};
+use crate::ApiVersion;
+
+/// v9 error bodies are a single JSON string, e.g. "Workspace not found".
+pub type ErrorJson = String;
+
+// https://developers.track.toggl.com/docs/api/time_entries
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimeEntry {
+    /// Only sent in responses
+    #[serde(skip_serializing)]
+    pub id: Option<TimeEntryId>,
+
+    /// workspace ID (required)
+    pub workspace_id: WorkspaceId,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<ProjectId>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<TaskId>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billable: Option<bool>,
+
+    /// start time (required)
+    pub start: DateTime<Utc>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<DateTime<Utc>>,
+
+    /// Duration in seconds. Negative for running time entries, as in v8.
+    pub duration: i64,
+
+    /// the name of your client app (required). Not always sent in responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_with: Option<String>,
+
+    /// tag names
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+
+    /// tag ids, an alternative to `tags`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_ids: Option<Vec<TagId>>,
+
+    /// Only sent in responses
+    #[serde(skip_serializing)]
+    pub user_id: Option<UserId>,
+
+    /// Only sent in responses. When the time entry was last updated.
+    #[serde(skip_serializing)]
+    pub at: Option<DateTime<Utc>>,
+}
+
+impl TimeEntry {
+    /// Whether the time entry is currently running (i.e., its duration is negative).
+    pub fn is_running(&self) -> bool {
+        return self.duration < 0;
+    }
+
+    /// The duration of the time entry, or the time elapsed since it was started if it's running.
+    pub fn elapsed(&self) -> Duration {
+        if self.is_running() {
+            return Utc::now() - self.start;
+        }
+        return Duration::seconds(self.duration);
+    }
+}
+
+// https://developers.track.toggl.com/docs/api/workspaces
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Workspace {
+    pub id: WorkspaceId,
+    pub organization_id: Option<OrganizationId>,
+    pub name: String,
+    pub premium: bool,
+    pub admin: bool,
+    pub default_hourly_rate: Option<f64>,
+    pub default_currency: String,
+    pub only_admins_may_create_projects: bool,
+    pub only_admins_see_billable_rates: bool,
+    pub rounding: i64,
+    pub rounding_minutes: i64,
+    pub at: DateTime<Utc>,
+    pub logo_url: Option<String>,
+}
+
+// https://developers.track.toggl.com/docs/api/tags
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tag {
+    pub id: TagId,
+    pub workspace_id: WorkspaceId,
+    pub name: String,
+    pub at: Option<DateTime<Utc>>,
+}
+
+// https://developers.track.toggl.com/docs/api/projects
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Project {
+    pub id: ProjectId,
+    pub workspace_id: WorkspaceId,
+    pub client_id: Option<ClientId>,
+    pub name: String,
+    pub is_private: bool,
+    pub active: bool,
+    pub billable: Option<bool>,
+    pub template: Option<bool>,
+    pub auto_estimates: Option<bool>,
+    pub estimated_hours: Option<i64>,
+    pub color: String,
+    pub rate: Option<f64>,
+    pub currency: Option<String>,
+    pub at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+// https://developers.track.toggl.com/docs/api/clients
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Client {
+    pub id: ClientId,
+    pub wid: WorkspaceId,
+    pub name: String,
+    pub at: DateTime<Utc>,
+}
+
+// https://developers.track.toggl.com/docs/api/me
+#[derive(Serialize, Deserialize, Clone)]
+pub struct User {
+    pub id: UserId,
+    pub api_token: String,
+    pub email: String,
+    pub fullname: String,
+    pub timezone: String,
+    pub default_workspace_id: WorkspaceId,
+    /// integer 0-6, Sunday=0
+    pub beginning_of_week: i64,
+    pub image_url: String,
+    pub at: DateTime<Utc>,
+
+    /// Only set when requested with `with_related_data`
+    pub clients: Option<Vec<Client>>,
+    pub projects: Option<Vec<Project>>,
+    pub tags: Option<Vec<Tag>>,
+    pub time_entries: Option<Vec<TimeEntry>>,
+    pub workspaces: Option<Vec<Workspace>>,
+}
+
+// The api token is kept out of logs and panic messages, like `Credentials`.
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f
+            .debug_struct("User")
+            .field("id", &self.id)
+            .field("api_token", &"REDACTED")
+            .field("email", &self.email)
+            .field("fullname", &self.fullname)
+            .field("timezone", &self.timezone)
+            .field("default_workspace_id", &self.default_workspace_id)
+            .field("beginning_of_week", &self.beginning_of_week)
+            .field("image_url", &self.image_url)
+            .field("at", &self.at)
+            .field("clients", &self.clients)
+            .field("projects", &self.projects)
+            .field("tags", &self.tags)
+            .field("time_entries", &self.time_entries)
+            .field("workspaces", &self.workspaces)
+            .finish();
+    }
+}
+
+/// The main Api object, for v9. Requests are sent the same way as those of `crate::api::Api`:
+/// rate limited, retried, and with the base URL and timeouts of the `ApiBuilder` it was built
+/// with.
+#[derive(Clone)]
+pub struct Api {
+    v8: crate::api::Api,
+}
+
+impl Api {
+    pub fn new(api_key: &str) -> Api {
+        return Self::with_credentials(Credentials::ApiToken(api_key.to_owned()));
+    }
+
+    pub fn with_credentials(credentials: Credentials) -> Api {
+        return Self::from_v8(crate::api::Api::with_credentials(credentials));
+    }
+
+    /// Start building an `Api` with another base URL, timeouts, ... Finish with
+    /// `ApiBuilder::build_v9`.
+    pub fn builder(api_key: &str) -> ApiBuilder {
+        return crate::api::Api::builder(api_key);
+    }
+
+    /// Send the requests of v9 with the executor of `v8`, which has the base URL of v9 too, and
+    /// opens its sessions with v9.
+    pub(crate) fn from_v8(v8: crate::api::Api) -> Api {
+        return Api {
+            v8: v8.with_version(ApiVersion::V9),
+        };
+    }
+
+    /// Create a time entry, in the workspace set on it.
+    pub fn time_entry_create(&self, time_entry: &TimeEntry) -> ApiResult<TimeEntry, ErrorJson> {
+        return self.v8.send(endpoints::time_entry_create(time_entry));
+    }
+
+    /// Get the workspaces of the current user
+    pub fn workspaces_get_all(&self) -> ApiResult<Vec<Workspace>, ErrorJson> {
+        return self.v8.send(endpoints::workspaces_get_all());
+    }
+
+    /// Get workspace tags. Toggl returns `null` instead of an empty list when there are no tags,
+    /// hence the `Option`.
+    pub fn workspaces_tags_all(&self, wid: WorkspaceId) -> ApiResult<Option<Vec<Tag>>, ErrorJson> {
+        return self.v8.send(endpoints::workspaces_tags_all(wid));
+    }
+
+    /// Get workspace projects. Same as with `workspaces_tags_all`, `None` means there are no
+    /// projects.
+    pub fn workspaces_projects_all(
+        &self,
+        wid: WorkspaceId,
+    ) -> ApiResult<Option<Vec<Project>>, ErrorJson> {
+        return self.v8.send(endpoints::workspaces_projects_all(wid));
+    }
+
+    /// Get current user, with their clients, projects, tags, time entries and workspaces if
+    /// `with_related_data` is set.
+    pub fn current_user(&self, with_related_data: bool) -> ApiResult<User, ErrorJson> {
+        return self.v8.send(endpoints::current_user(with_related_data));
+    }
+}
@@ -0,0 +1,1294 @@
+//! Tests of `Api` against a local stub of Toggl.
+mod common;
+
+use std::env;
+use std::fs;
+use std::time::Duration;
+
+use chrono::{Datelike, TimeZone, Utc};
+use serde_json::json;
+use toggl_oxide::api::{
+    ApiError, Client, ClientId, Credentials, ExportFormat, GroupId, Project, ProjectId,
+    ProjectUserId, ProjectsActive, ReportsDetailedParams, ReportsSummaryParams,
+    ReportsWeeklyParams, RetryPolicy, SummaryGroupId, SummaryGrouping, SummarySubgrouping, Tag,
+    TagId, TagRenameError, Task, TaskId, TasksActive, TimeEntry, TimeEntryId, TimeEntryPatch,
+    TimeEntryStart, UserId, UserUpdate, WeeklyCalculate, WeeklyGrouping, WorkspaceId,
+};
+
+use common::{Reply, Stub};
+
+fn workspace_json() -> serde_json::Value {
+    return json!({
+        "id": 3,
+        "name": "Work",
+        "premium": false,
+        "admin": true,
+        "default_hourly_rate": 0.0,
+        "default_currency": "USD",
+        "only_admins_may_create_projects": false,
+        "only_admins_see_billable_rates": false,
+        "rounding": 1,
+        "rounding_minutes": 0,
+        "at": "2021-12-01T09:00:00+00:00",
+        "logo_url": null,
+    });
+}
+
+fn time_entry() -> TimeEntry {
+    return TimeEntry {
+        id: None,
+        description: Some("Writing tests".to_owned()),
+        wid: Some(WorkspaceId(3)),
+        pid: None,
+        tid: None,
+        billable: None,
+        start: Utc.ymd(2021, 12, 1).and_hms(9, 0, 0),
+        stop: None,
+        duration: 3600,
+        created_with: Some("toggl_oxide".to_owned()),
+        tags: None,
+        duronly: None,
+        at: None,
+    };
+}
+
+#[test]
+fn requests_go_to_the_track_url_with_the_api_key() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([workspace_json()]))]);
+    let workspaces = common::api(&stub).workspaces_get_all().unwrap();
+    assert_eq!(workspaces.len(), 1);
+    assert_eq!(workspaces[0].name, "Work");
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path(), "/workspaces");
+    // secret:api_token
+    assert_eq!(
+        requests[0].header("Authorization"),
+        Some("Basic c2VjcmV0OmFwaV90b2tlbg==")
+    );
+}
+
+#[test]
+fn trailing_slashes_of_base_urls_are_ignored() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([]))]);
+    let api = common::builder(&stub)
+        .track_url(&(stub.url.clone() + "/api/v8/"))
+        .build()
+        .unwrap();
+    api.workspaces_get_all().unwrap();
+    assert_eq!(stub.requests()[0].path(), "/api/v8/workspaces");
+}
+
+#[test]
+fn the_user_agent_is_sent() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([]))]);
+    let api = common::builder(&stub)
+        .user_agent("toggl_oxide tests".to_owned())
+        .build()
+        .unwrap();
+    api.workspaces_get_all().unwrap();
+    assert_eq!(
+        stub.requests()[0].header("User-Agent"),
+        Some("toggl_oxide tests")
+    );
+}
+
+#[test]
+fn a_preconfigured_client_is_used() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([]))]);
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("custom client")
+        .build()
+        .unwrap();
+    let api = common::builder(&stub).client(client).build().unwrap();
+    api.workspaces_get_all().unwrap();
+    assert_eq!(
+        stub.requests()[0].header("User-Agent"),
+        Some("custom client")
+    );
+}
+
+#[test]
+fn requests_time_out() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([])).delay(Duration::from_millis(500))
+    ]);
+    let api = common::builder(&stub)
+        .timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let result = api.workspaces_get_all();
+    assert!(matches!(result, Err(ApiError::Network(_))), "{:?}", result);
+    stub.requests();
+}
+
+#[test]
+fn time_entries_are_created_with_a_wrapped_body() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({"data": {
+            "id": 11,
+            "wid": 3,
+            "description": "Writing tests",
+            "start": "2021-12-01T09:00:00+00:00",
+            "duration": 3600,
+        }}),
+    )]);
+    let created = common::api(&stub).time_entry_create(&time_entry()).unwrap();
+    assert_eq!(created.data.id, Some(TimeEntryId(11)));
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/time_entries");
+    let body = requests[0].json();
+    assert_eq!(body["time_entry"]["description"], "Writing tests");
+    assert_eq!(body["time_entry"]["wid"], 3);
+}
+
+#[test]
+fn time_entries_are_fetched_and_updated_by_id() {
+    let entry = json!({"data": {
+        "id": 11,
+        "wid": 3,
+        "description": "Writing tests",
+        "start": "2021-12-01T09:00:00+00:00",
+        "stop": "2021-12-01T10:00:00+00:00",
+        "duration": 3600,
+        "tags": ["tests"],
+        "at": "2021-12-01T10:00:00+00:00",
+    }});
+    let stub = Stub::serve(vec![
+        Reply::json(200, entry.clone()),
+        Reply::json(200, entry),
+    ]);
+    let api = common::api(&stub);
+    let fetched = api.time_entry_get(TimeEntryId(11)).unwrap().data;
+    assert_eq!(fetched.id, Some(TimeEntryId(11)));
+    assert_eq!(fetched.stop, Some(Utc.ymd(2021, 12, 1).and_hms(10, 0, 0)));
+    assert_eq!(fetched.tags, Some(vec!["tests".to_owned()]));
+    let updated = api
+        .time_entry_update(TimeEntryId(11), &time_entry())
+        .unwrap()
+        .data;
+    assert_eq!(updated.id, Some(TimeEntryId(11)));
+    assert_eq!(updated.description.as_deref(), Some("Writing tests"));
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path(), "/time_entries/11");
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/time_entries/11");
+    assert_eq!(
+        requests[1].json(),
+        json!({"time_entry": {
+            "description": "Writing tests",
+            "wid": 3,
+            "start": "2021-12-01T09:00:00Z",
+            "duration": 3600,
+            "created_with": "toggl_oxide",
+        }})
+    );
+}
+
+#[test]
+fn time_entries_are_listed_between_iso_8601_dates() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([]))]);
+    let entries = common::api(&stub)
+        .time_entries(
+            Utc.ymd(2021, 12, 1).and_hms(9, 0, 0),
+            Utc.ymd(2021, 12, 31).and_hms(23, 59, 59),
+        )
+        .unwrap();
+    assert!(entries.is_empty());
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/time_entries");
+    assert_eq!(
+        requests[0].param("start_date").as_deref(),
+        Some("2021-12-01T09:00:00Z")
+    );
+    assert_eq!(
+        requests[0].param("end_date").as_deref(),
+        Some("2021-12-31T23:59:59Z")
+    );
+    // A `+` left as is in a query string would be read back as a space.
+    let query = requests[0].url.split('?').nth(1).unwrap();
+    assert!(
+        query.contains("start_date=2021-12-01T09%3A00%3A00Z"),
+        "{}",
+        query
+    );
+    assert!(!query.contains(':') && !query.contains('+'), "{}", query);
+}
+
+fn running_time_entry_json() -> serde_json::Value {
+    return json!({
+        "id": 11,
+        "wid": 3,
+        "description": "Writing tests",
+        "start": "2021-12-01T09:00:00+00:00",
+        "duration": -1638349200,
+    });
+}
+
+#[test]
+fn time_entries_are_started_and_stopped() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!({"data": running_time_entry_json()})),
+        Reply::json(
+            200,
+            json!({"data": {
+                "id": 11,
+                "wid": 3,
+                "description": "Writing tests",
+                "start": "2021-12-01T09:00:00+00:00",
+                "stop": "2021-12-01T10:00:00+00:00",
+                "duration": 3600,
+            }}),
+        ),
+    ]);
+    let api = common::api(&stub);
+    let started = api
+        .time_entry_start(&TimeEntryStart {
+            description: Some("Writing tests".to_owned()),
+            wid: Some(WorkspaceId(3)),
+            created_with: "toggl_oxide".to_owned(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(started.data.is_running());
+    let stopped = api.time_entry_stop(TimeEntryId(11)).unwrap();
+    assert!(!stopped.data.is_running());
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/time_entries/start");
+    assert_eq!(
+        requests[0].json(),
+        json!({"time_entry": {
+            "description": "Writing tests",
+            "wid": 3,
+            "created_with": "toggl_oxide",
+        }})
+    );
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/time_entries/11/stop");
+    assert_eq!(requests[1].body, "");
+}
+
+#[test]
+fn the_current_time_entry_is_none_when_nothing_runs() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!({"data": running_time_entry_json()})),
+        Reply::json(200, json!({"data": null})),
+    ]);
+    let api = common::api(&stub);
+    let current = api.time_entry_current().unwrap();
+    assert_eq!(current.data.unwrap().id, Some(TimeEntryId(11)));
+    assert!(api.time_entry_current().unwrap().data.is_none());
+    assert_eq!(stub.requests()[0].path(), "/time_entries/current");
+}
+
+#[test]
+fn running_time_entries_have_elapsed_since_their_start() {
+    let mut entry = time_entry();
+    assert_eq!(entry.elapsed(), chrono::Duration::seconds(3600));
+
+    entry.duration = -(Utc::now().timestamp() - 90);
+    let elapsed = entry.elapsed().num_seconds();
+    assert!((90..=91).contains(&elapsed), "{}", elapsed);
+}
+
+fn project_json(id: i64) -> serde_json::Value {
+    return json!({"data": {
+        "id": id,
+        "name": "toggl_oxide",
+        "wid": 3,
+        "cid": 2,
+        "active": true,
+        "is_private": true,
+        "billable": false,
+        "at": "2021-12-01T09:00:00+00:00",
+        "color": "5",
+        "created_at": "2021-11-30T09:00:00+00:00",
+    }});
+}
+
+#[test]
+fn projects_are_created_without_their_read_only_fields() {
+    let stub = Stub::serve(vec![Reply::json(200, project_json(4))]);
+    // As fetched from Toggl, with fields that can't be sent back.
+    let project = Project {
+        id: Some(ProjectId(4)),
+        at: Some(Utc.ymd(2021, 12, 1).and_hms(9, 0, 0)),
+        created_at: Some(Utc.ymd(2021, 11, 30).and_hms(9, 0, 0)),
+        ..Project::new("toggl_oxide".to_owned(), WorkspaceId(3))
+    };
+    let created = common::api(&stub).project_create(&project).unwrap().data;
+    assert_eq!(created.id, Some(ProjectId(4)));
+    assert_eq!(
+        created.created_at,
+        Some(Utc.ymd(2021, 11, 30).and_hms(9, 0, 0))
+    );
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/projects");
+    let body = requests[0].json();
+    assert_eq!(body["project"]["name"], "toggl_oxide");
+    assert_eq!(body["project"]["wid"], 3);
+    for read_only in ["id", "at", "created_at"] {
+        assert!(body["project"].get(read_only).is_none(), "{}", body);
+    }
+}
+
+#[test]
+fn projects_are_fetched_updated_and_deleted_by_id() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, project_json(4)),
+        Reply::json(200, project_json(4)),
+        Reply::text(200, ""),
+    ]);
+    let api = common::api(&stub);
+    let mut project = api.project_get(ProjectId(4)).unwrap().data;
+    assert_eq!(project.name, "toggl_oxide");
+    assert_eq!(project.at, Some(Utc.ymd(2021, 12, 1).and_hms(9, 0, 0)));
+    project.billable = true;
+    let updated = api.project_update(ProjectId(4), &project).unwrap().data;
+    assert_eq!(updated.id, Some(ProjectId(4)));
+    api.project_delete(ProjectId(4)).unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path(), "/projects/4");
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/projects/4");
+    let body = requests[1].json();
+    assert_eq!(body["project"]["billable"], true);
+    for read_only in ["id", "at", "created_at"] {
+        assert!(body["project"].get(read_only).is_none(), "{}", body);
+    }
+    assert_eq!(requests[2].method, "DELETE");
+    assert_eq!(requests[2].path(), "/projects/4");
+}
+
+fn archived_project_json(id: i64) -> serde_json::Value {
+    return json!({"data": {
+        "id": id,
+        "name": "toggl_oxide",
+        "wid": 3,
+        "active": false,
+        "is_private": true,
+        "billable": false,
+    }});
+}
+
+#[test]
+fn archiving_projects_goes_on_after_a_failure() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, archived_project_json(1)),
+        Reply::json(404, json!(["Project not found"])),
+        Reply::json(200, archived_project_json(3)),
+    ]);
+    let result = common::api(&stub).projects_archive(&[ProjectId(1), ProjectId(2), ProjectId(3)]);
+    let archived: Vec<Option<ProjectId>> =
+        result.succeeded.iter().map(|project| project.id).collect();
+    assert_eq!(archived, vec![Some(ProjectId(1)), Some(ProjectId(3))]);
+    assert_eq!(result.failed_ids(), vec![ProjectId(2)]);
+
+    let requests = stub.requests();
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/projects/2");
+    assert_eq!(requests[1].json()["project"]["active"], false);
+}
+
+#[test]
+fn bulk_updates_are_chunked_and_report_failed_chunks_by_id() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!({"data": []})),
+        Reply::json(400, json!(["Time entry not found"])),
+    ]);
+    let ids: Vec<TimeEntryId> = (1..=101).map(TimeEntryId).collect();
+    let patch = TimeEntryPatch {
+        billable: Some(true),
+        ..Default::default()
+    };
+    let result = common::api(&stub).time_entries_bulk_update(&ids, &patch);
+    assert_eq!(result.failed_ids(), vec![TimeEntryId(101)]);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(
+        requests[0].path().ends_with(",100"),
+        "{}",
+        requests[0].path()
+    );
+    assert_eq!(requests[1].path(), "/time_entries/101");
+}
+
+#[test]
+fn bulk_deletes_are_chunked_and_report_failed_chunks_by_id() {
+    let stub = Stub::serve(vec![
+        Reply::text(200, ""),
+        Reply::json(404, json!(["Time entry not found"])),
+    ]);
+    let ids: Vec<TimeEntryId> = (1..=102).map(TimeEntryId).collect();
+    let result = common::api(&stub).time_entries_bulk_delete(&ids);
+    assert_eq!(
+        result.failed_ids(),
+        vec![TimeEntryId(101), TimeEntryId(102)]
+    );
+    assert_eq!(result.succeeded, ids[..100].to_vec());
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "DELETE");
+    let first_chunk: Vec<String> = (1..=100).map(|id| id.to_string()).collect();
+    assert_eq!(
+        requests[0].path(),
+        format!("/time_entries/{}", first_chunk.join(","))
+    );
+    assert_eq!(requests[1].method, "DELETE");
+    assert_eq!(requests[1].path(), "/time_entries/101,102");
+}
+
+/// A page of a detailed report with time entries tagged with `tags`.
+fn tagged_report_page(entries: &[(i64, &[&str])]) -> serde_json::Value {
+    let ids: Vec<i64> = entries.iter().map(|(id, _)| *id).collect();
+    let mut page = common::report_page(&ids, ids.len() as i64, 50);
+    for (entry, (_, tags)) in page["data"].as_array_mut().unwrap().iter_mut().zip(entries) {
+        entry["tags"] = json!(tags);
+    }
+    return page;
+}
+
+/// The year `tag_merge` is asked to search from: the year before last.
+fn merge_since_year() -> i32 {
+    return Utc::now().year() - 1;
+}
+
+/// The replies to the yearly reports `tag_merge` searches time entries with, from
+/// `merge_since_year` until next year, the first of which holds `entries`.
+fn tag_search_replies(entries: &[(i64, &[&str])]) -> Vec<Reply> {
+    let mut replies = vec![Reply::json(200, tagged_report_page(entries))];
+    for _ in merge_since_year() + 1..=Utc::now().year() + 1 {
+        replies.push(Reply::json(200, common::report_page(&[], 0, 50)));
+    }
+    return replies;
+}
+
+fn merge_since() -> chrono::DateTime<Utc> {
+    return Utc.ymd(merge_since_year(), 6, 1).and_hms(0, 0, 0);
+}
+
+fn into_tag() -> Tag {
+    return Tag {
+        id: Some(TagId(6)),
+        name: "rust".to_owned(),
+        wid: WorkspaceId(3),
+    };
+}
+
+#[test]
+fn merged_tags_are_searched_across_the_workspace_and_deleted_once_retagged() {
+    let mut replies = tag_search_replies(&[(1, &["rs"]), (2, &["rs", "rust"])]);
+    let search_count = replies.len();
+    replies.push(Reply::json(200, json!({"data": []})));
+    replies.push(Reply::text(200, ""));
+    let stub = Stub::serve(replies);
+    common::api(&stub)
+        .tag_merge(TagId(5), &into_tag(), merge_since(), "toggl_oxide")
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), search_count + 2);
+    let first_search = &requests[0];
+    assert_eq!(first_search.path(), "/reports/details");
+    assert_eq!(first_search.param("tag_ids").as_deref(), Some("5"));
+    let since_year = merge_since_year();
+    assert_eq!(search_count, 3);
+    assert_eq!(
+        first_search.param("since"),
+        Some(format!("{}-06-01", since_year))
+    );
+    assert_eq!(
+        first_search.param("until"),
+        Some(format!("{}-12-31", since_year))
+    );
+    assert_eq!(
+        requests[1].param("since"),
+        Some(format!("{}-01-01", since_year + 1))
+    );
+    let last_search = &requests[search_count - 1];
+    let next_year = (Utc::now().year() + 1).to_string();
+    assert!(last_search.param("until").unwrap().starts_with(&next_year));
+
+    // Time entry 2 is already tagged with "rust".
+    let retag = &requests[search_count];
+    assert_eq!(retag.method, "PUT");
+    assert_eq!(retag.path(), "/time_entries/1");
+    assert_eq!(retag.json()["time_entry"]["tags"], json!(["rust"]));
+    assert_eq!(retag.json()["time_entry"]["tag_action"], "add");
+    let delete = &requests[search_count + 1];
+    assert_eq!(delete.method, "DELETE");
+    assert_eq!(delete.path(), "/tags/5");
+}
+
+#[test]
+fn merged_tags_are_kept_if_retagging_fails() {
+    let mut replies = tag_search_replies(&[(1, &["rs"])]);
+    let search_count = replies.len();
+    replies.push(Reply::json(403, json!(["Forbidden"])));
+    let stub = Stub::serve(replies);
+    let result = common::builder(&stub)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+        .tag_merge(TagId(5), &into_tag(), merge_since(), "toggl_oxide");
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), search_count + 1);
+    assert!(requests.iter().all(|request| request.method != "DELETE"));
+}
+
+#[test]
+fn merged_tags_are_kept_if_searching_fails() {
+    let stub = Stub::serve(vec![Reply::text(500, "")]);
+    let result = common::builder(&stub)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+        .tag_merge(TagId(5), &into_tag(), merge_since(), "toggl_oxide");
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path(), "/reports/details");
+}
+
+#[test]
+fn tags_merged_into_themselves_are_left_alone() {
+    let stub = Stub::serve(vec![]);
+    let into = into_tag();
+    let retagged = common::api(&stub)
+        .tag_merge(into.id.unwrap(), &into, merge_since(), "toggl_oxide")
+        .unwrap();
+    assert!(retagged.is_empty());
+    let requests = stub.requests();
+    assert!(requests.iter().all(|request| request.method != "DELETE"));
+    assert!(requests.is_empty());
+}
+
+#[test]
+fn tags_are_renamed_by_id() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([{"id": 5, "name": "rs", "wid": 3}])),
+        Reply::json(200, json!({"data": {"id": 5, "name": "rust", "wid": 3}})),
+    ]);
+    let renamed = common::api(&stub)
+        .tag_rename(TagId(5), WorkspaceId(3), "rust")
+        .unwrap();
+    assert_eq!(renamed.name, "rust");
+
+    let requests = stub.requests();
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/tags/5");
+    assert_eq!(requests[1].json()["tag"]["name"], "rust");
+}
+
+#[test]
+fn tags_are_not_renamed_to_a_taken_name() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!([
+            {"id": 5, "name": "rs", "wid": 3},
+            {"id": 6, "name": "rust", "wid": 3},
+        ]),
+    )]);
+    let result = common::api(&stub).tag_rename(TagId(5), WorkspaceId(3), "rust");
+    match result {
+        Err(TagRenameError::NameTaken(taken)) => assert_eq!(taken.id, Some(TagId(6))),
+        _ => panic!("{:?}", result),
+    }
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path(), "/workspaces/3/tags");
+}
+
+fn task_json(id: i64) -> serde_json::Value {
+    return json!({"data": {
+        "id": id,
+        "name": "Write tests",
+        "pid": 4,
+        "wid": 3,
+        "estimated_seconds": 3600,
+        "active": true,
+        "at": "2021-12-01T09:00:00+00:00",
+        "tracked_seconds": 1800,
+    }});
+}
+
+#[test]
+fn tasks_are_created_without_their_read_only_fields() {
+    let stub = Stub::serve(vec![Reply::json(200, task_json(5))]);
+    let task = Task {
+        id: Some(TaskId(5)),
+        at: Some(Utc.ymd(2021, 12, 1).and_hms(9, 0, 0)),
+        tracked_seconds: Some(1800),
+        ..Task::new("Write tests".to_owned(), ProjectId(4))
+    };
+    let created = common::api(&stub).task_create(&task).unwrap().data;
+    assert_eq!(created.id, Some(TaskId(5)));
+    assert_eq!(created.tracked_seconds, Some(1800));
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/tasks");
+    assert_eq!(
+        requests[0].json(),
+        json!({"task": {"name": "Write tests", "pid": 4, "active": true}})
+    );
+}
+
+#[test]
+fn tasks_are_fetched_updated_and_deleted_by_id() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, task_json(5)),
+        Reply::json(200, task_json(5)),
+        Reply::text(200, ""),
+    ]);
+    let api = common::api(&stub);
+    let mut task = api.task_get(TaskId(5)).unwrap().data;
+    assert_eq!(task.name, "Write tests");
+    assert_eq!(task.estimated_seconds, Some(3600));
+    task.active = false;
+    let updated = api.task_update(TaskId(5), &task).unwrap().data;
+    assert_eq!(updated.id, Some(TaskId(5)));
+    api.task_delete(TaskId(5)).unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path(), "/tasks/5");
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/tasks/5");
+    assert_eq!(
+        requests[1].json(),
+        json!({"task": {
+            "name": "Write tests",
+            "pid": 4,
+            "wid": 3,
+            "estimated_seconds": 3600,
+            "active": false,
+        }})
+    );
+    assert_eq!(requests[2].method, "DELETE");
+    assert_eq!(requests[2].path(), "/tasks/5");
+}
+
+#[test]
+fn project_tasks_are_listed_by_activity() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([task_json(5)["data"]])),
+        Reply::json(200, json!(null)),
+    ]);
+    let api = common::api(&stub);
+    let tasks = api
+        .projects_tasks_all(ProjectId(4), TasksActive::True)
+        .unwrap()
+        .unwrap();
+    assert_eq!(tasks[0].id, Some(TaskId(5)));
+    let done = api
+        .projects_tasks_all(ProjectId(4), TasksActive::False)
+        .unwrap();
+    assert!(done.is_none());
+
+    let requests = stub.requests();
+    for (request, active) in requests.iter().zip(["true", "false"]) {
+        assert_eq!(request.path(), "/projects/4/tasks");
+        assert_eq!(request.param("active").as_deref(), Some(active));
+    }
+}
+
+#[test]
+fn task_names_include_done_tasks() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!([
+            {"id": 1, "name": "Active", "pid": 4, "active": true},
+            {"id": 2, "name": "Done", "pid": 4, "active": false},
+        ]),
+    )]);
+    let names = common::api(&stub)
+        .workspaces_task_names(WorkspaceId(3))
+        .unwrap();
+    assert_eq!(names[&TaskId(2)], "Done");
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/workspaces/3/tasks");
+    assert_eq!(requests[0].param("active").as_deref(), Some("both"));
+}
+
+#[test]
+fn workspace_users_are_listed() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!([{
+            "id": 7,
+            "email": "jane@example.com",
+            "fullname": "Jane",
+            "default_wid": 3,
+            "timezone": "Europe/Tallinn",
+            "image_url": null,
+            "at": "2021-12-01T09:00:00+00:00",
+        }]),
+    )]);
+    let users = common::api(&stub)
+        .workspaces_users_all(WorkspaceId(3))
+        .unwrap();
+    assert_eq!(users[0].id, UserId(7));
+    assert_eq!(users[0].fullname, "Jane");
+    assert_eq!(users[0].default_wid, Some(WorkspaceId(3)));
+    assert_eq!(users[0].timezone.as_deref(), Some("Europe/Tallinn"));
+    assert_eq!(stub.requests()[0].path(), "/workspaces/3/users");
+}
+
+#[test]
+fn project_users_are_listed_with_their_full_names() {
+    let stub = Stub::serve(vec![
+        Reply::json(
+            200,
+            json!([{
+                "id": 9,
+                "pid": 4,
+                "uid": 7,
+                "wid": 3,
+                "manager": true,
+                "rate": 25.5,
+                "at": "2021-12-01T09:00:00+00:00",
+                "fullname": "Jane",
+            }]),
+        ),
+        Reply::json(200, json!(null)),
+    ]);
+    let api = common::api(&stub);
+    let users = api.projects_users_all(ProjectId(4)).unwrap().unwrap();
+    assert_eq!(users[0].id, ProjectUserId(9));
+    assert_eq!(users[0].uid, UserId(7));
+    assert!(users[0].manager);
+    assert_eq!(users[0].rate, Some(25.5));
+    assert_eq!(users[0].fullname.as_deref(), Some("Jane"));
+    assert!(api.projects_users_all(ProjectId(5)).unwrap().is_none());
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/projects/4/project_users");
+    assert_eq!(requests[0].param("fields").as_deref(), Some("fullname"));
+}
+
+#[test]
+fn workspace_groups_are_listed() {
+    let stub = Stub::serve(vec![
+        Reply::json(
+            200,
+            json!([{"id": 2, "name": "Developers", "wid": 3, "at": "2021-12-01T09:00:00+00:00"}]),
+        ),
+        Reply::json(200, json!(null)),
+    ]);
+    let api = common::api(&stub);
+    let groups = api.workspaces_groups_all(WorkspaceId(3)).unwrap().unwrap();
+    assert_eq!(groups[0].id, GroupId(2));
+    assert_eq!(groups[0].name, "Developers");
+    assert_eq!(groups[0].wid, WorkspaceId(3));
+    assert!(api.workspaces_groups_all(WorkspaceId(4)).unwrap().is_none());
+    assert_eq!(stub.requests()[0].path(), "/workspaces/3/groups");
+}
+
+#[test]
+fn the_dashboard_lists_active_users_and_recent_activity() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({
+            "most_active_user": [
+                {"user_id": 7, "duration": 28800},
+                {"user_id": 8, "duration": 3600},
+            ],
+            "activity": [
+                {
+                    "user_id": 7,
+                    "project_id": 4,
+                    "tid": null,
+                    "duration": 1800,
+                    "description": "Writing tests",
+                    "stop": "2021-12-01T09:30:00+00:00",
+                },
+                {
+                    "user_id": 8,
+                    "project_id": null,
+                    "tid": 2,
+                    "duration": -1638349200,
+                    "description": null,
+                    "stop": null,
+                },
+            ],
+        }),
+    )]);
+    let dashboard = common::api(&stub).dashboard(WorkspaceId(3)).unwrap();
+    let most_active = dashboard.most_active_user.unwrap();
+    assert_eq!(most_active[0].user_id, UserId(7));
+    assert_eq!(most_active[0].duration, 28800);
+    let activity = dashboard.activity.unwrap();
+    assert_eq!(activity[0].project_id, Some(ProjectId(4)));
+    assert!(!activity[0].is_running());
+    assert_eq!(activity[0].elapsed(), chrono::Duration::seconds(1800));
+    assert_eq!(activity[1].tid, Some(TaskId(2)));
+    assert!(activity[1].is_running());
+    assert_eq!(activity[1].stop, None);
+
+    assert_eq!(stub.requests()[0].path(), "/dashboard/3");
+}
+
+#[test]
+fn workspaces_without_clients_have_none() {
+    let stub = Stub::serve(vec![Reply::json(200, json!(null))]);
+    let clients = common::api(&stub)
+        .workspaces_clients_all(WorkspaceId(3))
+        .unwrap();
+    assert!(clients.is_none());
+    assert_eq!(stub.requests()[0].path(), "/workspaces/3/clients");
+}
+
+fn client_json(id: i64) -> serde_json::Value {
+    return json!({"data": {
+        "id": id,
+        "wid": 3,
+        "name": "Very Big Company",
+        "notes": "Contact Stephanie",
+        "at": "2021-12-01T09:00:00+00:00",
+    }});
+}
+
+#[test]
+fn clients_are_created_without_their_read_only_fields() {
+    let stub = Stub::serve(vec![Reply::json(200, client_json(2))]);
+    let client = Client {
+        id: Some(ClientId(2)),
+        at: Some(Utc.ymd(2021, 12, 1).and_hms(9, 0, 0)),
+        ..Client::new("Very Big Company".to_owned(), WorkspaceId(3))
+    };
+    let created = common::api(&stub).client_create(&client).unwrap().data;
+    assert_eq!(created.id, Some(ClientId(2)));
+    assert_eq!(created.notes.as_deref(), Some("Contact Stephanie"));
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/clients");
+    let body = requests[0].json();
+    assert_eq!(
+        body,
+        json!({"client": {"wid": 3, "name": "Very Big Company"}})
+    );
+}
+
+#[test]
+fn clients_are_fetched_updated_and_deleted_by_id() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, client_json(2)),
+        Reply::json(200, client_json(2)),
+        Reply::text(200, ""),
+    ]);
+    let api = common::api(&stub);
+    let mut client = api.client_get(ClientId(2)).unwrap().data;
+    assert_eq!(client.name, "Very Big Company");
+    assert_eq!(client.at, Some(Utc.ymd(2021, 12, 1).and_hms(9, 0, 0)));
+    client.notes = Some("Contact Ann".to_owned());
+    let updated = api.client_update(ClientId(2), &client).unwrap().data;
+    assert_eq!(updated.id, Some(ClientId(2)));
+    api.client_delete(ClientId(2)).unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path(), "/clients/2");
+    assert_eq!(requests[1].method, "PUT");
+    assert_eq!(requests[1].path(), "/clients/2");
+    assert_eq!(
+        requests[1].json(),
+        json!({"client": {"wid": 3, "name": "Very Big Company", "notes": "Contact Ann"}})
+    );
+    assert_eq!(requests[2].method, "DELETE");
+    assert_eq!(requests[2].path(), "/clients/2");
+}
+
+#[test]
+fn client_projects_are_listed_by_activity() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([project_json(4)["data"]])),
+        Reply::json(200, json!(null)),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::api(&stub);
+    let projects = api
+        .client_projects(ClientId(2), ProjectsActive::True)
+        .unwrap()
+        .unwrap();
+    assert_eq!(projects[0].id, Some(ProjectId(4)));
+    let archived = api
+        .client_projects(ClientId(2), ProjectsActive::False)
+        .unwrap();
+    assert!(archived.is_none());
+    api.client_projects(ClientId(2), ProjectsActive::Both)
+        .unwrap();
+
+    let requests = stub.requests();
+    for (request, active) in requests.iter().zip(["true", "false", "both"]) {
+        assert_eq!(request.path(), "/clients/2/projects");
+        assert_eq!(request.param("active").as_deref(), Some(active));
+    }
+}
+
+fn user_json() -> serde_json::Value {
+    return json!({
+        "id": 7,
+        "api_token": "secret",
+        "default_wid": 3,
+        "email": "jane@example.com",
+        "fullname": "Jane Doe",
+        "jquery_timeofday_format": "H:i",
+        "jquery_date_format": "m/d/Y",
+        "timeofday_format": "H:mm",
+        "date_format": "MM/DD/YYYY",
+        "store_start_and_stop_time": true,
+        "beginning_of_week": 1,
+        "language": "en_US",
+        "image_url": "https://assets.toggl.com/avatars/7.png",
+        "sidebar_piechart": false,
+        "at": "2021-12-01T09:00:00+00:00",
+        "send_product_emails": false,
+        "send_weekly_report": true,
+        "send_timer_notifications": true,
+        "openid_enabled": false,
+        "timezone": "Europe/Tallinn",
+    });
+}
+
+#[test]
+fn the_current_user_is_updated() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({"since": 1638349200, "data": user_json()}),
+    )]);
+    let update = UserUpdate {
+        fullname: Some("Jane Doe".to_owned()),
+        beginning_of_week: Some(1),
+        ..Default::default()
+    };
+    let user = common::api(&stub)
+        .current_user_update(&update)
+        .unwrap()
+        .data;
+    assert_eq!(user.id, UserId(7));
+    assert_eq!(user.fullname, "Jane Doe");
+    assert_eq!(user.beginning_of_week, 1);
+    assert_eq!(user.default_wid, WorkspaceId(3));
+    assert_eq!(user.api_token, "secret");
+    let debugged = format!("{:?}", user);
+    assert!(!debugged.contains("secret"), "{}", debugged);
+    assert!(debugged.contains("Jane Doe"), "{}", debugged);
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "PUT");
+    assert_eq!(requests[0].path(), "/me");
+    assert_eq!(
+        requests[0].json(),
+        json!({"user": {"fullname": "Jane Doe", "beginning_of_week": 1}})
+    );
+}
+
+#[test]
+fn resetting_the_api_token_authenticates_with_the_new_one() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!("fresh")),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::api(&stub).reset_api_token().unwrap();
+    assert!(api.credentials() == &Credentials::ApiToken("fresh".to_owned()));
+    api.workspaces_get_all().unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/reset_token");
+    // fresh:api_token
+    assert_eq!(
+        requests[1].header("Authorization"),
+        Some("Basic ZnJlc2g6YXBpX3Rva2Vu")
+    );
+    assert_eq!(requests[1].path(), "/workspaces");
+}
+
+#[test]
+fn deletes_accept_an_empty_body() {
+    let stub = Stub::serve(vec![Reply::text(200, "")]);
+    common::api(&stub)
+        .time_entry_delete(TimeEntryId(11))
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[0].path(), "/time_entries/11");
+}
+
+#[test]
+fn error_statuses_are_server_errors() {
+    let stub = Stub::serve(vec![Reply::text(403, "")]);
+    let result = common::api(&stub).time_entry_get(TimeEntryId(11));
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    stub.requests();
+}
+
+#[test]
+fn toggl_error_messages_are_server_errors() {
+    let stub = Stub::serve(vec![Reply::json(200, json!(["Time entry not found"]))]);
+    let result = common::api(&stub).time_entry_get(TimeEntryId(11));
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    stub.requests();
+}
+
+#[test]
+fn unexpected_bodies_are_parsing_errors() {
+    let stub = Stub::serve(vec![Reply::json(200, json!({"unexpected": true}))]);
+    let result = common::api(&stub).time_entry_get(TimeEntryId(11));
+    assert!(matches!(result, Err(ApiError::Parsing(_))), "{:?}", result);
+    stub.requests();
+}
+
+#[test]
+fn detailed_reports_are_paged_through_on_the_reports_url() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, common::report_page(&[1, 2], 3, 2)),
+        Reply::json(200, common::report_page(&[3], 3, 2)),
+    ]);
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+    let entries = common::api(&stub).reports_detailed_all(params).unwrap();
+    let ids: Vec<i64> = entries.iter().map(|entry| entry.id.0).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path(), "/reports/details");
+    assert_eq!(requests[0].param("page").as_deref(), Some("1"));
+    assert_eq!(requests[1].param("page").as_deref(), Some("2"));
+    assert_eq!(
+        requests[0].param("user_agent").as_deref(),
+        Some("toggl_oxide")
+    );
+    assert_eq!(requests[0].param("workspace_id").as_deref(), Some("3"));
+}
+
+#[test]
+fn detailed_report_iterators_end_after_a_failing_page() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, common::report_page(&[1, 2], 6, 2)),
+        Reply::json(
+            400,
+            json!({"error": {"message": "bad page", "tip": "", "code": 400}}),
+        ),
+    ]);
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+    let api = common::api(&stub);
+    let mut entries = api.reports_detailed_iter(params);
+    assert_eq!(entries.next().unwrap().unwrap().id.0, 1);
+    assert_eq!(entries.next().unwrap().unwrap().id.0, 2);
+    let failure = entries.next().unwrap();
+    assert!(matches!(failure, Err(ApiError::Server(_))), "{:?}", failure);
+    assert!(entries.next().is_none());
+    assert_eq!(stub.requests().len(), 2);
+}
+
+#[test]
+fn detailed_report_iterators_end_at_an_empty_page() {
+    // Entries deleted while paging leave fewer than `total_count`.
+    let stub = Stub::serve(vec![
+        Reply::json(200, common::report_page(&[1, 2], 6, 2)),
+        Reply::json(200, common::report_page(&[], 6, 2)),
+    ]);
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+    let api = common::api(&stub);
+    let ids: Vec<i64> = api
+        .reports_detailed_iter(params)
+        .map(|entry| entry.unwrap().id.0)
+        .collect();
+    assert_eq!(ids, vec![1, 2]);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].param("page").as_deref(), Some("2"));
+}
+
+#[test]
+fn summary_reports_are_grouped() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({
+            "total_grand": 21604000,
+            "total_billable": 14400000,
+            "total_currencies": [{"currency": "EUR", "amount": 40}],
+            "data": [
+                {
+                    "id": 193009951,
+                    "title": {"project": "Toggl Development", "client": null},
+                    "time": 14400000,
+                    "total_currencies": [{"currency": "EUR", "amount": 40}],
+                    "items": [{
+                        "title": {"time_entry": "Hard work"},
+                        "time": 14400000,
+                        "cur": "EUR",
+                        "sum": 40,
+                        "rate": 10,
+                        "local_start": "2013-05-29T16:00:00"
+                    }]
+                },
+                {
+                    "id": null,
+                    "title": {"project": null, "client": null},
+                    "time": 7204000,
+                    "total_currencies": [],
+                    "items": [{
+                        "title": {"time_entry": "No project"},
+                        "time": 7204000,
+                        "cur": null,
+                        "sum": null,
+                        "rate": null
+                    }]
+                }
+            ]
+        }),
+    )]);
+    let params = ReportsSummaryParams::new(
+        "toggl_oxide".to_owned(),
+        WorkspaceId(3),
+        SummaryGrouping::Projects,
+        SummarySubgrouping::TimeEntries,
+    );
+    let report = common::api(&stub).reports_summary(&params).unwrap();
+    assert_eq!(report.total_grand, Some(21604000));
+    assert_eq!(report.data[0].id, Some(SummaryGroupId(193009951)));
+    assert_eq!(
+        report.data[0].title.project.as_deref(),
+        Some("Toggl Development")
+    );
+    assert_eq!(
+        report.data[0].items[0].title.time_entry.as_deref(),
+        Some("Hard work")
+    );
+    assert_eq!(report.data[0].items[0].sum, Some(40.0));
+    assert_eq!(report.data[1].id, None);
+    assert_eq!(report.data[1].title.project, None);
+    assert_eq!(report.data[1].items[0].cur, None);
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/reports/summary");
+    assert_eq!(requests[0].param("workspace_id").as_deref(), Some("3"));
+    assert_eq!(requests[0].param("grouping").as_deref(), Some("projects"));
+    assert_eq!(
+        requests[0].param("subgrouping").as_deref(),
+        Some("time_entries")
+    );
+}
+
+#[test]
+fn weekly_reports_have_a_total_per_day() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({
+            "total_grand": 36004000,
+            "total_billable": null,
+            "total_currencies": [{"currency": null, "amount": null}],
+            "week_totals": [4000, 30000000, null, null, null, null, 6004000, 36004000],
+            "data": [{
+                "title": {
+                    "client": "Andromeda",
+                    "project": "Toggl Desktop",
+                    "color": "0",
+                    "hex_color": "#4dc3ff"
+                },
+                "pid": 1234,
+                "totals": [4000, 30000000, null, null, null, null, 6004000, 36004000],
+                "details": [{
+                    "uid": 7,
+                    "title": {"user": "Jane"},
+                    "totals": [4000, 30000000, null, null, null, null, 6004000, 36004000]
+                }]
+            }]
+        }),
+    )]);
+    let params = ReportsWeeklyParams::new(
+        "toggl_oxide".to_owned(),
+        WorkspaceId(3),
+        WeeklyGrouping::Projects,
+        WeeklyCalculate::Time,
+    );
+    let report = common::api(&stub).reports_weekly(&params).unwrap();
+    assert_eq!(report.total_currencies[0].amount, None);
+    assert_eq!(report.week_totals.len(), 8);
+    assert_eq!(report.week_totals[1], Some(30000000.0));
+    assert_eq!(report.week_totals[2], None);
+    let row = &report.data[0];
+    assert_eq!(row.pid, Some(ProjectId(1234)));
+    assert_eq!(row.uid, None);
+    assert_eq!(row.title.project.as_deref(), Some("Toggl Desktop"));
+    assert_eq!(row.title.hex_color.as_deref(), Some("#4dc3ff"));
+    assert_eq!(row.details[0].uid, Some(UserId(7)));
+    assert_eq!(row.details[0].title.user.as_deref(), Some("Jane"));
+    assert_eq!(row.details[0].totals[7], Some(36004000.0));
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/reports/weekly");
+    assert_eq!(requests[0].param("grouping").as_deref(), Some("projects"));
+    assert_eq!(requests[0].param("calculate").as_deref(), Some("time"));
+}
+
+#[test]
+fn reports_are_exported_to_a_writer() {
+    let stub = Stub::serve(vec![Reply::text(200, "date,duration\n2021-12-01,3600\n")]);
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+    let mut csv = vec![];
+    let written = common::api(&stub)
+        .reports_export(&params, ExportFormat::Csv, &mut csv)
+        .unwrap();
+    assert_eq!(written, csv.len() as u64);
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "date,duration\n2021-12-01,3600\n"
+    );
+    assert_eq!(stub.requests()[0].path(), "/reports/details.csv");
+}
+
+#[test]
+fn failed_exports_leave_the_file_untouched() {
+    let path = env::temp_dir().join(format!("toggl_oxide_export_{}.csv", std::process::id()));
+    fs::write(&path, "previous export").unwrap();
+    let stub = Stub::serve(vec![
+        Reply::text(500, ""),
+        Reply::text(200, "date,duration\n"),
+    ]);
+    let api = common::builder(&stub)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+
+    let result = api.reports_export_to_file(&params, ExportFormat::Csv, &path);
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "previous export");
+
+    api.reports_export_to_file(&params, ExportFormat::Csv, &path)
+        .unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "date,duration\n");
+    let mut partial_name = path.file_name().unwrap().to_owned();
+    partial_name.push(".part");
+    assert!(!path.with_file_name(partial_name).exists());
+    fs::remove_file(&path).unwrap();
+    stub.requests();
+}
@@ -0,0 +1,146 @@
+//! Tests of `AsyncApi`, against a local stub of Toggl. Run with `--features async`.
+#![cfg(feature = "async")]
+mod common;
+
+use std::env;
+use std::fs;
+
+use reqwest::StatusCode;
+use serde_json::json;
+use toggl_oxide::api::{
+    ApiError, Credentials, ErrorKind, ExportFormat, ReportsDetailedParams, RetryPolicy,
+    TimeEntryId, WorkspaceId,
+};
+
+use common::{Reply, Stub};
+
+#[tokio::test]
+async fn requests_are_sent_and_responses_parsed() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!([{
+            "id": 3,
+            "name": "Work",
+            "premium": false,
+            "admin": true,
+            "default_hourly_rate": 0.0,
+            "default_currency": "USD",
+            "only_admins_may_create_projects": false,
+            "only_admins_see_billable_rates": false,
+            "rounding": 1,
+            "rounding_minutes": 0,
+            "at": "2021-12-01T09:00:00+00:00",
+            "logo_url": null,
+        }]),
+    )]);
+    let api = common::builder(&stub).build_async().unwrap();
+    let workspaces = api.workspaces_get_all().await.unwrap();
+    assert_eq!(workspaces[0].id, Some(WorkspaceId(3)));
+    assert_eq!(workspaces[0].name, "Work");
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path(), "/workspaces");
+    assert!(requests[0].header("Authorization").is_some());
+}
+
+#[tokio::test]
+async fn errors_are_mapped_like_blocking_ones() {
+    let stub = Stub::serve(vec![
+        Reply::json(404, json!(["Time entry not found"])),
+        Reply::text(503, "Down for maintenance"),
+    ]);
+    let api = common::builder(&stub)
+        .retry_policy(RetryPolicy::none())
+        .build_async()
+        .unwrap();
+
+    let err = api.time_entry_get(TimeEntryId(11)).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert_eq!(err.status_code(), Some(StatusCode::NOT_FOUND));
+    assert_eq!(err.messages(), vec!["Time entry not found".to_owned()]);
+
+    let err = api.time_entry_get(TimeEntryId(11)).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ServerFault);
+    assert_eq!(err.text(), Some("Down for maintenance"));
+    assert_eq!(stub.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn detailed_reports_are_paged_through() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, common::report_page(&[1, 2], 3, 2)),
+        Reply::json(200, common::report_page(&[3], 3, 2)),
+    ]);
+    let api = common::builder(&stub).build_async().unwrap();
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+    let entries = api.reports_detailed_all(params).await.unwrap();
+    let ids: Vec<i64> = entries.iter().map(|entry| entry.id.0).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].path(), "/reports/details");
+    assert_eq!(requests[0].param("page").as_deref(), Some("1"));
+    assert_eq!(requests[1].param("page").as_deref(), Some("2"));
+}
+
+#[tokio::test]
+async fn reports_are_exported_to_files() {
+    let path = env::temp_dir().join(format!(
+        "toggl_oxide_async_export_{}.csv",
+        std::process::id()
+    ));
+    fs::write(&path, "previous export").unwrap();
+    let stub = Stub::serve(vec![
+        Reply::text(500, ""),
+        Reply::text(200, "date,duration\n"),
+    ]);
+    let api = common::builder(&stub)
+        .retry_policy(RetryPolicy::none())
+        .build_async()
+        .unwrap();
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+
+    let result = api
+        .reports_export_to_file(&params, ExportFormat::Csv, &path)
+        .await;
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "previous export");
+
+    let written = api
+        .reports_export_to_file(&params, ExportFormat::Csv, &path)
+        .await
+        .unwrap();
+    assert_eq!(written, 14);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "date,duration\n");
+    fs::remove_file(&path).unwrap();
+    stub.requests();
+}
+
+#[tokio::test]
+async fn logins_open_a_session() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!({"data": {}})).header("Set-Cookie", "toggl_api_session_new=abc"),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::builder_with(
+        &stub,
+        Credentials::Login {
+            email: "jane@example.com".to_owned(),
+            password: "hunter2".to_owned(),
+        },
+    )
+    .build_async()
+    .unwrap();
+    api.workspaces_get_all().await.unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/sessions");
+    assert_eq!(
+        requests[1].header("Cookie"),
+        Some("toggl_api_session_new=abc")
+    );
+    assert_eq!(requests[1].header("Authorization"), None);
+}
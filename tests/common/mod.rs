@@ -0,0 +1,218 @@
+//! A local HTTP stub that stands in for Toggl: it answers requests with canned replies, in order,
+//! and records what it was sent.
+#![allow(dead_code)]
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use tiny_http::{Header, Response, Server};
+use toggl_oxide::api::{Api, ApiBuilder, Credentials};
+
+/// The API key the tests authenticate with.
+pub const API_KEY: &str = "secret";
+
+/// How long the stub waits for a request before giving up on the remaining replies.
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A canned reply of the stub.
+#[derive(Debug, Clone)]
+pub struct Reply {
+    pub status: u16,
+    pub body: String,
+    pub headers: Vec<(String, String)>,
+    /// How long to wait before replying
+    pub delay: Duration,
+}
+
+impl Reply {
+    pub fn json(status: u16, body: serde_json::Value) -> Self {
+        return Self {
+            status,
+            body: body.to_string(),
+            headers: vec![("Content-Type".to_owned(), "application/json".to_owned())],
+            delay: Duration::ZERO,
+        };
+    }
+
+    pub fn text(status: u16, body: &str) -> Self {
+        return Self {
+            status,
+            body: body.to_owned(),
+            headers: vec![],
+            delay: Duration::ZERO,
+        };
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        return self;
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        return self;
+    }
+}
+
+/// A request the stub received.
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    /// The path and query string
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Recorded {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        return self
+            .headers
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str());
+    }
+
+    pub fn path(&self) -> &str {
+        return self.url.split('?').next().unwrap();
+    }
+
+    /// The value of the query param `name`.
+    pub fn param(&self, name: &str) -> Option<String> {
+        let url = reqwest::Url::parse(&format!("http://stub{}", self.url)).unwrap();
+        return url
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned());
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        return serde_json::from_str(&self.body).unwrap();
+    }
+}
+
+pub struct Stub {
+    /// The URL the stub listens on, e.g. http://127.0.0.1:34567
+    pub url: String,
+    requests: mpsc::Receiver<Recorded>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl Stub {
+    /// Start a stub that answers the next requests with `replies`, one per request.
+    pub fn serve(replies: Vec<Reply>) -> Self {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (sender, requests) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            for reply in replies {
+                let mut request = match server.recv_timeout(RECV_TIMEOUT) {
+                    Ok(Some(request)) => request,
+                    _ => return,
+                };
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let recorded = Recorded {
+                    method: request.method().to_string(),
+                    url: request.url().to_owned(),
+                    headers: request
+                        .headers()
+                        .iter()
+                        .map(|header| (header.field.to_string(), header.value.to_string()))
+                        .collect(),
+                    body,
+                };
+                sender.send(recorded).unwrap();
+
+                thread::sleep(reply.delay);
+                let mut response = Response::from_string(reply.body).with_status_code(reply.status);
+                for (name, value) in &reply.headers {
+                    response
+                        .add_header(Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap());
+                }
+                // The client may have given up already (timeouts).
+                let _ = request.respond(response);
+            }
+        });
+        return Self {
+            url,
+            requests,
+            handle,
+        };
+    }
+
+    /// The URL to use as reports base URL.
+    pub fn reports_url(&self) -> String {
+        return self.url.clone() + "/reports";
+    }
+
+    /// The URL to use as base URL of v9.
+    pub fn v9_url(&self) -> String {
+        return self.url.clone() + "/v9";
+    }
+
+    /// The requests the stub received, once it's done replying.
+    pub fn requests(self) -> Vec<Recorded> {
+        self.handle.join().unwrap();
+        return self.requests.try_iter().collect();
+    }
+}
+
+/// A builder of `Api`s that send their requests to `stub`, without rate limiting to keep the
+/// tests fast.
+pub fn builder(stub: &Stub) -> ApiBuilder {
+    return builder_with(stub, Credentials::ApiToken(API_KEY.to_owned()));
+}
+
+/// Same as `builder`, authenticating with `credentials`.
+pub fn builder_with(stub: &Stub, credentials: Credentials) -> ApiBuilder {
+    return ApiBuilder::new(credentials)
+        .track_url(&stub.url)
+        .reports_url(&stub.reports_url())
+        .v9_url(&stub.v9_url())
+        .min_request_interval(Duration::ZERO);
+}
+
+/// An `Api` that sends its requests to `stub`.
+pub fn api(stub: &Stub) -> Api {
+    return builder(stub).build().unwrap();
+}
+
+/// A time entry of a detailed report, as sent by Toggl.
+pub fn report_time_entry(id: i64) -> serde_json::Value {
+    return serde_json::json!({
+        "id": id,
+        "pid": null,
+        "project": null,
+        "client": null,
+        "tid": null,
+        "task": null,
+        "uid": 7,
+        "user": "Jane",
+        "description": "Writing tests",
+        "start": "2021-12-01T09:00:00+00:00",
+        "end": "2021-12-01T10:00:00+00:00",
+        "dur": 3600000,
+        "updated": "2021-12-01T10:00:00+00:00",
+        "use_stop": true,
+        "is_billable": false,
+        "billable": 0.0,
+        "cur": "USD",
+        "tags": [],
+        "project_color": "0",
+        "project_hex_color": null,
+    });
+}
+
+/// A page of a detailed report.
+pub fn report_page(ids: &[i64], total_count: i64, per_page: i64) -> serde_json::Value {
+    let data: Vec<serde_json::Value> = ids.iter().map(|id| report_time_entry(*id)).collect();
+    return serde_json::json!({
+        "total_grand": null,
+        "total_billable": null,
+        "total_count": total_count,
+        "per_page": per_page,
+        "total_currencies": [],
+        "data": data,
+    });
+}
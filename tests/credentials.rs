@@ -0,0 +1,214 @@
+//! Tests of the credentials `Api` authenticates with, and of sharing it.
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+
+use serde_json::json;
+use toggl_oxide::api::{Api, ConfigError, Credentials, ErrorKind, RetryPolicy};
+
+use common::{Reply, Stub};
+
+fn login(password: &str) -> Credentials {
+    return Credentials::Login {
+        email: "jane@example.com".to_owned(),
+        password: password.to_owned(),
+    };
+}
+
+/// Write a config file with `contents`, named after the test so tests don't step on each other.
+/// Tests remove it once read.
+fn config_file(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("toggl_oxide_{}_{}.env", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    return path;
+}
+
+#[test]
+fn apis_are_cloneable_and_shareable_across_threads() {
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+    assert_shareable::<Api>();
+
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([])),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::api(&stub);
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let api = api.clone();
+            thread::spawn(move || api.workspaces_get_all().unwrap())
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(stub.requests().len(), 2);
+}
+
+#[test]
+fn logins_open_a_session_and_send_its_cookie() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!({"since": 1638349200, "data": {}}))
+            .header("Set-Cookie", "toggl_api_session_new=abc; Path=/; HttpOnly"),
+        Reply::json(200, json!([])),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::builder_with(&stub, login("hunter2"))
+        .build()
+        .unwrap();
+    api.workspaces_get_all().unwrap();
+    api.workspaces_get_all().unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/sessions");
+    // jane@example.com:hunter2
+    assert_eq!(
+        requests[0].header("Authorization"),
+        Some("Basic amFuZUBleGFtcGxlLmNvbTpodW50ZXIy")
+    );
+    for request in &requests[1..] {
+        assert_eq!(request.header("Cookie"), Some("toggl_api_session_new=abc"));
+        assert_eq!(request.header("Authorization"), None);
+    }
+}
+
+#[test]
+fn refused_sessions_are_opened_again() {
+    let session = |value: &str| {
+        return Reply::json(200, json!({"data": {}}))
+            .header("Set-Cookie", &format!("toggl_api_session_new={}", value));
+    };
+    let stub = Stub::serve(vec![
+        session("abc"),
+        Reply::text(403, ""),
+        session("def"),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::builder_with(&stub, login("hunter2"))
+        .build()
+        .unwrap();
+    api.workspaces_get_all().unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[2].path(), "/sessions");
+    assert_eq!(requests[3].path(), "/workspaces");
+    assert_eq!(
+        requests[3].header("Cookie"),
+        Some("toggl_api_session_new=def")
+    );
+}
+
+#[test]
+fn sessions_are_opened_again_only_once() {
+    let session =
+        Reply::json(200, json!({"data": {}})).header("Set-Cookie", "toggl_api_session_new=abc");
+    let stub = Stub::serve(vec![
+        session.clone(),
+        Reply::text(403, ""),
+        session,
+        Reply::text(403, ""),
+    ]);
+    let result = common::builder_with(&stub, login("hunter2"))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+        .workspaces_get_all();
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Auth);
+    assert_eq!(stub.requests().len(), 4);
+}
+
+#[test]
+fn failed_logins_are_errors() {
+    let stub = Stub::serve(vec![Reply::text(403, "")]);
+    let result = common::builder_with(&stub, login("wrong"))
+        .build()
+        .unwrap()
+        .workspaces_get_all();
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::Auth);
+    assert_eq!(stub.requests().len(), 1);
+}
+
+#[test]
+fn config_files_hold_an_api_token_or_a_login() {
+    let path = config_file(
+        "token",
+        "TOGGL_API_KEY=secret\nTOGGL_EMAIL=jane@example.com\n",
+    );
+    let credentials = Credentials::from_config_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        credentials.unwrap(),
+        Credentials::ApiToken("secret".to_owned())
+    );
+
+    let path = config_file(
+        "login",
+        "# Jane's account\nTOGGL_EMAIL=jane@example.com\nTOGGL_PASSWORD=hunter2\n",
+    );
+    let credentials = Credentials::from_config_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        credentials.unwrap(),
+        Credentials::Login {
+            email: "jane@example.com".to_owned(),
+            password: "hunter2".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn config_files_may_export_and_quote_their_entries() {
+    let path = config_file(
+        "quoted",
+        "export TOGGL_EMAIL=\"jane@example.com\"\n\nTOGGL_PASSWORD = 'hunter2 # not a comment'\n",
+    );
+    let credentials = Credentials::from_config_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        credentials.unwrap(),
+        Credentials::Login {
+            email: "jane@example.com".to_owned(),
+            password: "hunter2 # not a comment".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn incomplete_or_missing_config_files_are_errors() {
+    let path = config_file("incomplete", "TOGGL_EMAIL=jane@example.com\n");
+    let result = Credentials::from_config_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(
+        matches!(result, Err(ConfigError::MissingCredentials)),
+        "{:?}",
+        result
+    );
+
+    let result = Api::from_config_file(env::temp_dir().join("toggl_oxide_nonexistent.env"));
+    assert!(matches!(result, Err(ConfigError::File(_))));
+
+    let path = config_file("syntax", "# Jane's account\nTOGGL_API_KEY\n");
+    let result = Credentials::from_config_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(
+        matches!(result, Err(ConfigError::Syntax { line: 2 })),
+        "{:?}",
+        result
+    );
+}
+
+#[test]
+fn secrets_are_not_debug_printed() {
+    let credentials = Credentials::Login {
+        email: "jane@example.com".to_owned(),
+        password: "hunter2".to_owned(),
+    };
+    assert!(!format!("{:?}", credentials).contains("hunter2"));
+    assert!(!format!("{:?}", Credentials::ApiToken("secret".to_owned())).contains("secret"));
+}
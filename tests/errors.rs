@@ -0,0 +1,161 @@
+//! Tests of the errors of `Api`, against a local stub of Toggl.
+mod common;
+
+use std::error::Error;
+
+use reqwest::StatusCode;
+use serde_json::json;
+use toggl_oxide::api::{
+    ApiError, ErrorKind, ReportsDetailedParams, ReportsErrorJson, RetryPolicy, Tag, TagId,
+    TimeEntryId, WorkspaceId,
+};
+
+use common::{Reply, Stub};
+
+#[test]
+fn statuses_are_classified() {
+    let cases = [
+        (401, ErrorKind::Auth),
+        (403, ErrorKind::Auth),
+        (404, ErrorKind::NotFound),
+        (400, ErrorKind::Validation),
+        (500, ErrorKind::ServerFault),
+    ];
+    for (status, kind) in cases {
+        let stub = Stub::serve(vec![Reply::text(status, "Nope")]);
+        let err = common::api(&stub)
+            .time_entry_get(TimeEntryId(11))
+            .unwrap_err();
+        assert_eq!(err.kind(), kind, "{}", status);
+        assert_eq!(err.status_code(), StatusCode::from_u16(status).ok());
+        assert_eq!(err.text(), Some("Nope"));
+        stub.requests();
+    }
+}
+
+#[test]
+fn toggl_messages_are_exposed() {
+    let stub = Stub::serve(vec![Reply::json(200, json!(["Time entry not found"]))]);
+    let err = common::api(&stub)
+        .time_entry_get(TimeEntryId(11))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Validation);
+    assert_eq!(err.messages(), vec!["Time entry not found".to_owned()]);
+    assert_eq!(
+        err.to_string(),
+        "Toggl answered 200 OK: Time entry not found"
+    );
+    stub.requests();
+}
+
+#[test]
+fn network_errors_are_classified() {
+    // Nothing listens there once the stub is done.
+    let stub = Stub::serve(vec![]);
+    let api = common::builder(&stub)
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    stub.requests();
+    let err = api.workspaces_get_all().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Network);
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn parsing_errors_chain_to_serde() {
+    let stub = Stub::serve(vec![Reply::json(200, json!({"unexpected": true}))]);
+    let err = common::api(&stub)
+        .time_entry_get(TimeEntryId(11))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Other);
+    let parsing = err.source().unwrap();
+    let serde = parsing.source().unwrap();
+    assert!(serde.is::<serde_json::Error>());
+    stub.requests();
+}
+
+#[test]
+fn errors_can_be_boxed() {
+    fn first_report_page(api: &toggl_oxide::api::Api) -> Result<i64, Box<dyn Error>> {
+        let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+        return Ok(api.reports_detailed(&params)?.total_count);
+    }
+
+    let stub = Stub::serve(vec![Reply::text(500, "")]);
+    let err = first_report_page(&common::api(&stub)).unwrap_err();
+    let err = err
+        .downcast_ref::<ApiError<ReportsErrorJson>>()
+        .map(ApiError::kind);
+    assert_eq!(err, Some(ErrorKind::ServerFault));
+    stub.requests();
+}
+
+#[test]
+fn error_bodies_are_parsed_whatever_the_status() {
+    let stub = Stub::serve(vec![Reply::json(404, json!(["Workspace not found"]))]);
+    let err = common::api(&stub)
+        .workspaces_tags_all(WorkspaceId(3))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert_eq!(err.messages(), vec!["Workspace not found".to_owned()]);
+    assert_eq!(
+        err.to_string(),
+        "Toggl answered 404 Not Found: Workspace not found"
+    );
+    stub.requests();
+}
+
+#[test]
+fn reports_error_bodies_are_parsed() {
+    let stub = Stub::serve(vec![Reply::json(
+        400,
+        json!({"error": {
+            "message": "We are sorry, this Error should never happen to you",
+            "tip": "Please contact support@toggl.com with information on your request",
+            "code": 400,
+        }}),
+    )]);
+    let params = ReportsDetailedParams::new("toggl_oxide".to_owned(), WorkspaceId(3), 1);
+    let err = common::api(&stub).reports_detailed(&params).unwrap_err();
+    let parsed = match &err {
+        ApiError::Server(server_error) => server_error.parsed_json().unwrap(),
+        other => panic!("Expected a server error, got {:?}", other),
+    };
+    assert_eq!(parsed.code(), 400);
+    assert_eq!(
+        parsed.message(),
+        "We are sorry, this Error should never happen to you"
+    );
+    assert_eq!(err.kind(), ErrorKind::Validation);
+    stub.requests();
+}
+
+#[test]
+fn error_bodies_in_another_shape_are_kept_as_text() {
+    let stub = Stub::serve(vec![Reply::text(400, "<html>Bad request</html>")]);
+    let err = common::api(&stub)
+        .time_entry_get(TimeEntryId(11))
+        .unwrap_err();
+    assert!(err.messages().is_empty());
+    assert_eq!(err.text(), Some("<html>Bad request</html>"));
+    stub.requests();
+}
+
+#[test]
+fn any_2xx_status_is_a_success() {
+    let stub = Stub::serve(vec![
+        Reply::json(201, json!({"data": {"id": 5, "name": "rust", "wid": 3}})),
+        Reply::text(204, ""),
+    ]);
+    let api = common::api(&stub);
+    let tag = Tag {
+        id: None,
+        name: "rust".to_owned(),
+        wid: WorkspaceId(3),
+    };
+    let created = api.tag_create(&tag).unwrap();
+    assert_eq!(created.data.id, Some(TagId(5)));
+    api.tag_delete(TagId(5)).unwrap();
+    assert_eq!(stub.requests().len(), 2);
+}
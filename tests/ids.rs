@@ -0,0 +1,88 @@
+//! Tests of the id newtypes: they must look like plain numbers to Toggl and to the database.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::sqlite::SqliteConnection;
+use serde_json::json;
+use toggl_oxide::api::{Project, ProjectId, TaskId, TimeEntryId, WorkspaceId};
+
+// `schema.rs` isn't part of the library yet. diesel 1.4's `table!` wraps its impls in functions.
+#[allow(dead_code, non_local_definitions)]
+mod schema {
+    include!("../src/schema.rs");
+}
+
+/// A database with every migration applied.
+fn migrated_db() -> SqliteConnection {
+    let conn = SqliteConnection::establish(":memory:").unwrap();
+    for up in [
+        include_str!("../migrations/2021-11-23-094316_create_models/up.sql"),
+        include_str!("../migrations/2021-12-01-120000_create_tasks/up.sql"),
+    ] {
+        conn.batch_execute(up).unwrap();
+    }
+    return conn;
+}
+
+#[test]
+fn ids_serialize_as_numbers() {
+    let project = Project::new("toggl_oxide".to_owned(), WorkspaceId(3));
+    assert_eq!(serde_json::to_value(&project).unwrap()["wid"], json!(3));
+
+    let ids: Vec<TaskId> = serde_json::from_value(json!([1, 2])).unwrap();
+    assert_eq!(ids, vec![TaskId(1), TaskId(2)]);
+}
+
+#[test]
+fn ids_round_trip_through_bigint_columns() {
+    let conn = SqliteConnection::establish(":memory:").unwrap();
+    let id: ProjectId = diesel::select(ProjectId(42).into_sql::<BigInt>())
+        .get_result(&conn)
+        .unwrap();
+    assert_eq!(id, ProjectId(42));
+}
+
+#[test]
+fn ids_too_large_for_integer_columns_are_not_truncated() {
+    use schema::time_entrys::dsl::*;
+
+    let conn = migrated_db();
+    let large = TimeEntryId(1 << 40);
+    diesel::insert_into(time_entrys)
+        .values((
+            id.eq(large),
+            description.eq("Writing tests"),
+            wid.eq(WorkspaceId(1 << 33)),
+            start.eq("2021-12-01T09:00:00+00:00"),
+            duration.eq(3600),
+        ))
+        .execute(&conn)
+        .unwrap();
+    let stored: (Option<TimeEntryId>, Option<WorkspaceId>) =
+        time_entrys.select((id, wid)).first(&conn).unwrap();
+    assert_eq!(stored, (Some(large), Some(WorkspaceId(1 << 33))));
+}
+
+#[test]
+fn missing_ids_are_assigned_by_the_database() {
+    use schema::time_entrys::dsl::*;
+
+    let conn = migrated_db();
+    for _ in 0..2 {
+        diesel::insert_into(time_entrys)
+            .values((
+                id.eq(None::<TimeEntryId>),
+                description.eq("Writing tests"),
+                start.eq("2021-12-01T09:00:00+00:00"),
+                duration.eq(3600),
+            ))
+            .execute(&conn)
+            .unwrap();
+    }
+    let ids: Vec<Option<TimeEntryId>> = time_entrys.select(id).load(&conn).unwrap();
+    assert_eq!(ids, vec![Some(TimeEntryId(1)), Some(TimeEntryId(2))]);
+}
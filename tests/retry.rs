@@ -0,0 +1,124 @@
+//! Tests of the rate limiting and retries of `Api`, against a local stub of Toggl.
+mod common;
+
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use toggl_oxide::api::{ApiError, RetryPolicy, Tag, TimeEntryId, WorkspaceId};
+
+use common::{Reply, Stub};
+
+/// Retries without waiting long.
+fn quick_retries(max_retries: u32) -> RetryPolicy {
+    return RetryPolicy {
+        max_retries,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+    };
+}
+
+fn tag() -> Tag {
+    return Tag {
+        id: None,
+        name: "rust".to_owned(),
+        wid: WorkspaceId(3),
+    };
+}
+
+#[test]
+fn rate_limited_requests_are_retried_after_retry_after() {
+    let stub = Stub::serve(vec![
+        Reply::text(429, "").header("Retry-After", "1"),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::builder(&stub)
+        .retry_policy(quick_retries(3))
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    api.workspaces_get_all().unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(stub.requests().len(), 2);
+}
+
+#[test]
+fn unavailable_servers_are_retried_until_the_retries_run_out() {
+    let stub = Stub::serve(vec![
+        Reply::text(503, ""),
+        Reply::text(502, ""),
+        Reply::text(503, ""),
+    ]);
+    let api = common::builder(&stub)
+        .retry_policy(quick_retries(2))
+        .build()
+        .unwrap();
+    let result = api.workspaces_get_all();
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    assert_eq!(stub.requests().len(), 3);
+}
+
+#[test]
+fn client_errors_are_not_retried() {
+    let stub = Stub::serve(vec![Reply::text(404, "")]);
+    let api = common::builder(&stub)
+        .retry_policy(quick_retries(3))
+        .build()
+        .unwrap();
+    let result = api.time_entry_get(TimeEntryId(11));
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    assert_eq!(stub.requests().len(), 1);
+}
+
+#[test]
+fn non_idempotent_requests_are_not_retried() {
+    let stub = Stub::serve(vec![Reply::text(503, "")]);
+    let api = common::builder(&stub)
+        .retry_policy(quick_retries(3))
+        .build()
+        .unwrap();
+    let result = api.tag_create(&tag());
+    assert!(matches!(result, Err(ApiError::Server(_))), "{:?}", result);
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+}
+
+#[test]
+fn network_errors_are_retried() {
+    // The stub is still busy with the first, timed out request when the retry is sent, so the
+    // backoff must outlast the delay of the first reply.
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([])).delay(Duration::from_millis(300)),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::builder(&stub)
+        .timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(800),
+            max_backoff: Duration::from_secs(1),
+        })
+        .build()
+        .unwrap();
+    api.workspaces_get_all().unwrap();
+    assert_eq!(stub.requests().len(), 2);
+}
+
+#[test]
+fn requests_are_spaced_out() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!([])),
+        Reply::json(200, json!([])),
+        Reply::json(200, json!([])),
+    ]);
+    let api = common::builder(&stub)
+        .min_request_interval(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    for _ in 0..3 {
+        api.workspaces_get_all().unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(400));
+    assert_eq!(stub.requests().len(), 3);
+}
@@ -0,0 +1,155 @@
+//! Tests that `src/schema.rs` is what `diesel print-schema` generates from the migrations, once
+//! `src/schema.patch` (`print_schema.patch_file` in diesel.toml) is applied: regenerating the
+//! schema must keep the `BigInt` id columns.
+// diesel 1.4's derives wrap their impls in functions.
+#![allow(non_local_definitions)]
+
+#[macro_use]
+extern crate diesel;
+
+use std::fs;
+use std::path::Path;
+
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Text};
+use diesel::sqlite::SqliteConnection;
+
+#[derive(QueryableByName)]
+struct TableName {
+    #[sql_type = "Text"]
+    name: String,
+}
+
+#[derive(QueryableByName)]
+struct ColumnInfo {
+    #[sql_type = "Text"]
+    name: String,
+    #[sql_type = "Text"]
+    #[column_name = "type"]
+    type_name: String,
+    #[sql_type = "Integer"]
+    notnull: i32,
+    #[sql_type = "Integer"]
+    pk: i32,
+}
+
+/// A database with every migration applied, in order.
+fn migrated_db() -> SqliteConnection {
+    let conn = SqliteConnection::establish(":memory:").unwrap();
+    let migrations = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+    let mut dirs: Vec<_> = fs::read_dir(migrations)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        conn.batch_execute(&fs::read_to_string(dir.join("up.sql")).unwrap())
+            .unwrap();
+    }
+    return conn;
+}
+
+/// The diesel type of a column of SQLite type `type_name`, as `print-schema` infers it.
+fn diesel_type(type_name: &str) -> &'static str {
+    let type_name = type_name.to_lowercase();
+    return match type_name.as_str() {
+        "boolean" => "Bool",
+        "bigint" => "BigInt",
+        "real" => "Float",
+        "text" => "Text",
+        _ if type_name.contains("int") => "Integer",
+        _ => panic!("no diesel type for {}", type_name),
+    };
+}
+
+/// The `table!` blocks `print-schema` generates out of `conn`.
+fn printed_tables(conn: &SqliteConnection) -> String {
+    let tables: Vec<TableName> = diesel::sql_query(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+         AND name != '__diesel_schema_migrations' ORDER BY name",
+    )
+    .load(conn)
+    .unwrap();
+    let mut blocks = vec![];
+    for table in tables {
+        let columns: Vec<ColumnInfo> =
+            diesel::sql_query(format!("PRAGMA table_info({})", table.name))
+                .load(conn)
+                .unwrap();
+        // In the order of the columns, rather than of the PRIMARY KEY clause.
+        let primary_key: Vec<&str> = columns
+            .iter()
+            .filter(|column| column.pk > 0)
+            .map(|column| column.name.as_str())
+            .collect();
+
+        let mut block = format!(
+            "table! {{\n    {} ({}) {{\n",
+            table.name,
+            primary_key.join(", ")
+        );
+        for column in &columns {
+            let sql_type = diesel_type(&column.type_name);
+            let sql_type = match column.notnull {
+                0 => format!("Nullable<{}>", sql_type),
+                _ => sql_type.to_owned(),
+            };
+            block += &format!("        {} -> {},\n", column.name, sql_type);
+        }
+        block += "    }\n}\n";
+        blocks.push(block);
+    }
+    return blocks.join("\n");
+}
+
+/// Apply the hunks of the unified diff `patch` to `text`, in order.
+fn apply_patch(text: &str, patch: &str) -> String {
+    let mut result = text.to_owned();
+    let mut cursor = 0;
+    let hunks = patch.split("\n@@").skip(1);
+    for hunk in hunks {
+        let (mut old, mut new) = (String::new(), String::new());
+        for line in hunk.lines().skip(1) {
+            let (marker, content) = line.split_at(line.len().min(1));
+            match marker {
+                " " | "" => {
+                    old += content;
+                    old += "\n";
+                    new += content;
+                    new += "\n";
+                }
+                "-" => {
+                    old += content;
+                    old += "\n";
+                }
+                "+" => {
+                    new += content;
+                    new += "\n";
+                }
+                _ => {}
+            }
+        }
+        let start = cursor
+            + result[cursor..]
+                .find(&old)
+                .unwrap_or_else(|| panic!("hunk doesn't apply:\n{}", old));
+        result.replace_range(start..start + old.len(), &new);
+        cursor = start + new.len();
+    }
+    return result;
+}
+
+#[test]
+fn the_schema_is_the_patched_printed_schema() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let schema = fs::read_to_string(root.join("src/schema.rs")).unwrap();
+    let patch = fs::read_to_string(root.join("src/schema.patch")).unwrap();
+    // Only the `table!`s are printed here: the `joinable!`s and the rest are taken as they are.
+    let (schema_tables, rest) = schema.split_at(schema.find("\njoinable!").unwrap());
+
+    let printed = printed_tables(&migrated_db());
+    assert_ne!(printed, schema_tables, "the patch changes nothing");
+    assert_eq!(apply_patch(&(printed + rest), &patch), schema);
+}
@@ -0,0 +1,263 @@
+//! Tests of `v9::Api`, against a local stub of Toggl.
+mod common;
+
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use toggl_oxide::api::{
+    ApiError, Credentials, ErrorKind, ProjectId, RetryPolicy, TagId, TimeEntryId, UserId,
+    WorkspaceId,
+};
+use toggl_oxide::v9::TimeEntry;
+use toggl_oxide::ApiVersion;
+
+use common::{Reply, Stub};
+
+#[test]
+fn v9_requests_are_sent_like_v8_ones() {
+    let stub = Stub::serve(vec![Reply::text(503, ""), Reply::json(200, json!([]))]);
+    let api = common::builder(&stub)
+        .retry_policy(RetryPolicy {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+        })
+        .build_v9()
+        .unwrap();
+    assert!(api.workspaces_get_all().unwrap().is_empty());
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].path(), "/v9/me/workspaces");
+    assert!(requests[1].header("Authorization").is_some());
+}
+
+fn workspace(id: i64) -> serde_json::Value {
+    return json!({
+        "id": id,
+        "organization_id": null,
+        "name": "Home",
+        "premium": false,
+        "admin": true,
+        "default_hourly_rate": null,
+        "default_currency": "USD",
+        "only_admins_may_create_projects": false,
+        "only_admins_see_billable_rates": false,
+        "rounding": 1,
+        "rounding_minutes": 0,
+        "at": "2021-12-01T10:00:00+00:00",
+        "logo_url": null,
+    });
+}
+
+fn project(id: i64) -> serde_json::Value {
+    return json!({
+        "id": id,
+        "workspace_id": 3,
+        "client_id": null,
+        "name": "toggl_oxide",
+        "is_private": true,
+        "active": true,
+        "billable": null,
+        "template": null,
+        "auto_estimates": null,
+        "estimated_hours": null,
+        "color": "#06aaf5",
+        "rate": null,
+        "currency": null,
+        "at": "2021-12-01T10:00:00+00:00",
+        "created_at": "2021-11-01T10:00:00+00:00",
+    });
+}
+
+#[test]
+fn time_entries_are_created_in_their_workspace_without_a_wrapper() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({
+            "id": 11,
+            "workspace_id": 3,
+            "description": "Writing tests",
+            "start": "2021-12-01T09:00:00Z",
+            "stop": null,
+            "duration": -1638349200,
+            "tags": ["rust"],
+            "user_id": 7,
+            "at": "2021-12-01T09:00:00Z",
+        }),
+    )]);
+    let time_entry = TimeEntry {
+        id: None,
+        workspace_id: WorkspaceId(3),
+        description: Some("Writing tests".to_owned()),
+        project_id: None,
+        task_id: None,
+        billable: None,
+        start: Utc.ymd(2021, 12, 1).and_hms(9, 0, 0),
+        stop: None,
+        duration: -1638349200,
+        created_with: Some("toggl_oxide".to_owned()),
+        tags: Some(vec!["rust".to_owned()]),
+        tag_ids: None,
+        user_id: None,
+        at: None,
+    };
+    let created = common::builder(&stub)
+        .build_v9()
+        .unwrap()
+        .time_entry_create(&time_entry)
+        .unwrap();
+    assert_eq!(created.id, Some(TimeEntryId(11)));
+    assert!(created.is_running());
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/v9/workspaces/3/time_entries");
+    let body = requests[0].json();
+    assert_eq!(body["workspace_id"], 3);
+    assert_eq!(body["description"], "Writing tests");
+    assert!(body.get("time_entry").is_none());
+    assert!(body.get("id").is_none());
+}
+
+#[test]
+fn workspaces_are_unwrapped() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([workspace(3)]))]);
+    let workspaces = common::builder(&stub)
+        .build_v9()
+        .unwrap()
+        .workspaces_get_all()
+        .unwrap();
+    assert_eq!(workspaces.len(), 1);
+    assert_eq!(workspaces[0].id, WorkspaceId(3));
+    assert_eq!(stub.requests()[0].path(), "/v9/me/workspaces");
+}
+
+#[test]
+fn logins_open_a_v9_session() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!({"id": 7})).header(
+            "Set-Cookie",
+            "__Host-timer-session=abc; Path=/; Secure; HttpOnly",
+        ),
+        Reply::json(200, json!([workspace(3)])),
+    ]);
+    let api = common::builder_with(
+        &stub,
+        Credentials::Login {
+            email: "jane@example.com".to_owned(),
+            password: "hunter2".to_owned(),
+        },
+    )
+    .build_v9()
+    .unwrap();
+    api.workspaces_get_all().unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path(), "/v9/me/sessions");
+    // jane@example.com:hunter2
+    assert_eq!(
+        requests[0].header("Authorization"),
+        Some("Basic amFuZUBleGFtcGxlLmNvbTpodW50ZXIy")
+    );
+    assert_eq!(requests[1].path(), "/v9/me/workspaces");
+    assert_eq!(
+        requests[1].header("Cookie"),
+        Some("__Host-timer-session=abc")
+    );
+    assert_eq!(requests[1].header("Authorization"), None);
+}
+
+#[test]
+fn null_tags_are_none() {
+    let stub = Stub::serve(vec![
+        Reply::json(200, json!(null)),
+        Reply::json(
+            200,
+            json!([{"id": 5, "workspace_id": 3, "name": "rust", "at": null}]),
+        ),
+    ]);
+    let api = common::builder(&stub).build_v9().unwrap();
+    assert!(api.workspaces_tags_all(WorkspaceId(3)).unwrap().is_none());
+    let tags = api.workspaces_tags_all(WorkspaceId(3)).unwrap().unwrap();
+    assert_eq!(tags[0].id, TagId(5));
+    assert_eq!(stub.requests()[0].path(), "/v9/workspaces/3/tags");
+}
+
+#[test]
+fn projects_are_unwrapped() {
+    let stub = Stub::serve(vec![Reply::json(200, json!([project(13)]))]);
+    let projects = common::builder(&stub)
+        .build_v9()
+        .unwrap()
+        .workspaces_projects_all(WorkspaceId(3))
+        .unwrap()
+        .unwrap();
+    assert_eq!(projects[0].id, ProjectId(13));
+    assert_eq!(stub.requests()[0].path(), "/v9/workspaces/3/projects");
+}
+
+#[test]
+fn the_current_user_can_come_with_related_data() {
+    let stub = Stub::serve(vec![Reply::json(
+        200,
+        json!({
+            "id": 7,
+            "api_token": "secret",
+            "email": "jane@example.com",
+            "fullname": "Jane",
+            "timezone": "Europe/Paris",
+            "default_workspace_id": 3,
+            "beginning_of_week": 1,
+            "image_url": "https://example.com/jane.png",
+            "at": "2021-12-01T10:00:00+00:00",
+            "clients": null,
+            "projects": [project(13)],
+            "tags": null,
+            "time_entries": null,
+            "workspaces": [workspace(3)],
+        }),
+    )]);
+    let user = common::builder(&stub)
+        .build_v9()
+        .unwrap()
+        .current_user(true)
+        .unwrap();
+    assert_eq!(user.id, UserId(7));
+    assert_eq!(user.projects.unwrap()[0].id, ProjectId(13));
+    let requests = stub.requests();
+    assert_eq!(requests[0].path(), "/v9/me");
+    assert_eq!(
+        requests[0].param("with_related_data").as_deref(),
+        Some("true")
+    );
+}
+
+#[test]
+fn errors_are_single_strings() {
+    let stub = Stub::serve(vec![Reply::json(404, json!("Workspace not found"))]);
+    let err = common::builder(&stub)
+        .build_v9()
+        .unwrap()
+        .workspaces_projects_all(WorkspaceId(3))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    match &err {
+        ApiError::Server(server_error) => {
+            assert_eq!(
+                server_error.parsed_json(),
+                Some(&"Workspace not found".to_owned())
+            )
+        }
+        other => panic!("Expected a server error, got {:?}", other),
+    }
+    stub.requests();
+}
+
+#[test]
+fn versions_are_parsed() {
+    assert_eq!("v8".parse::<ApiVersion>(), Ok(ApiVersion::V8));
+    assert_eq!("v9".parse::<ApiVersion>(), Ok(ApiVersion::V9));
+    assert!("v10".parse::<ApiVersion>().is_err());
+}